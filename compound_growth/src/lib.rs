@@ -5,9 +5,7 @@ pub fn add(left: u64, right: u64) -> u64 {
 pub fn compund_growth(start: f64, daily_percent: f64, days: u32) -> f64 {
     let daily_rate = daily_percent / 100.0;
     let r = start * (1.0 + daily_rate).powi(days as i32);
-    let rounded = (r * 100.0).round() / 100.0;
-
-    rounded
+    (r * 100.0).round() / 100.0
 }
 
 #[cfg(test)]
@@ -20,6 +20,27 @@ pub fn ema(data: &[u64], a: f64) -> Vec<f64> {
     r
 }
 
+/// EMA of float prices, aligned with `data`. It is seeded with the first
+/// price, so every entry is `Some`; an invalid `a` gives all `None`.
+pub fn ema_f64(data: &[f64], a: f64) -> Vec<Option<f64>> {
+    if a <= 0.0 || a > 1.0 {
+        return vec![None; data.len()];
+    }
+
+    let mut prev_ema: Option<f64> = None;
+    data.iter()
+        .map(|&value| {
+            let ema = match prev_ema {
+                Some(prev) => a * value + (1.0 - a) * prev,
+                None => value,
+            };
+            prev_ema = Some(ema);
+            prev_ema
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone)]
 pub struct Ema {
     pub alpha: f64,
     pub value: Option<f64>,
@@ -43,11 +64,38 @@ impl Ema {
     pub fn get(&self) -> Option<f64> {
         self.value
     }
+
+    pub fn reset(&mut self) {
+        self.value = None;
+    }
+}
+
+impl indicator::Indicator for Ema {
+    type Input = f64;
+    type Output = f64;
+
+    fn next(&mut self, price: f64) -> Option<f64> {
+        Some(Ema::next(self, price))
+    }
+
+    fn value(&self) -> Option<f64> {
+        self.value
+    }
+
+    fn reset(&mut self) {
+        Ema::reset(self)
+    }
+
+    /// Seeded with the first price, so ready straight away.
+    fn warm_up(&self) -> usize {
+        1
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use indicator::testing::TestRng;
 
     #[test]
     fn it_works() {
@@ -108,4 +156,25 @@ mod tests {
         let expected = vec![100.0, 110.0];
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_ema_f64_basic() {
+        let result = ema_f64(&[0.0004, 0.0008], 0.5);
+        assert_eq!(result[0], Some(0.0004));
+        assert!((result[1].unwrap() - 0.0006).abs() < 1e-12);
+        assert_eq!(ema_f64(&[1.0, 2.0], 0.0), vec![None, None]);
+    }
+
+    #[test]
+    fn test_ema_f64_matches_streaming() {
+        // Property test over pseudo-random series and periods
+        let mut rng = TestRng::new(11);
+        for _ in 0..200 {
+            let period = rng.range(1, 31);
+            let data = rng.series(60, 1000.0);
+            let mut streaming_ema = Ema::new(period);
+            let batch = ema_f64(&data, streaming_ema.alpha);
+            assert_eq!(batch, indicator::run(&mut streaming_ema, data.iter().copied()));
+        }
+    }
 }
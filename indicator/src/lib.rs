@@ -0,0 +1,83 @@
+#[cfg(feature = "testing")]
+pub mod testing;
+
+/// Common shape of every streaming indicator: feed one input at a time and
+/// read the latest output once enough inputs have been seen.
+pub trait Indicator {
+    /// What is fed in on each step, e.g. a close price or a whole candle.
+    type Input;
+    type Output;
+
+    /// Feed the next input. Returns `None` while the indicator is warming up.
+    fn next(&mut self, input: Self::Input) -> Option<Self::Output>;
+
+    /// Latest output, if the indicator is ready.
+    fn value(&self) -> Option<Self::Output>;
+
+    fn is_ready(&self) -> bool {
+        self.value().is_some()
+    }
+
+    /// Forget all inputs, keeping the configuration.
+    fn reset(&mut self);
+
+    /// Number of inputs needed before the first output.
+    fn warm_up(&self) -> usize;
+}
+
+/// Run `indicator` over `inputs`, returning one output per input
+/// (`None` during warm-up) so the result lines up with the inputs.
+pub fn run<I, It>(indicator: &mut I, inputs: It) -> Vec<Option<I::Output>>
+where
+    I: Indicator,
+    It: IntoIterator<Item = I::Input>,
+{
+    inputs.into_iter().map(|input| indicator.next(input)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sum of the last two inputs.
+    struct PairSum {
+        prev: Option<f64>,
+        value: Option<f64>,
+    }
+
+    impl Indicator for PairSum {
+        type Input = f64;
+        type Output = f64;
+
+        fn next(&mut self, input: f64) -> Option<f64> {
+            self.value = self.prev.map(|p| p + input);
+            self.prev = Some(input);
+            self.value
+        }
+
+        fn value(&self) -> Option<f64> {
+            self.value
+        }
+
+        fn reset(&mut self) {
+            self.prev = None;
+            self.value = None;
+        }
+
+        fn warm_up(&self) -> usize {
+            2
+        }
+    }
+
+    #[test]
+    fn test_run_aligns_with_inputs() {
+        let mut ind = PairSum { prev: None, value: None };
+        assert!(!ind.is_ready());
+        assert_eq!(run(&mut ind, [1.0, 2.0, 3.0]), vec![None, Some(3.0), Some(5.0)]);
+        assert!(ind.is_ready());
+
+        ind.reset();
+        assert_eq!(ind.value(), None);
+        assert_eq!(run(&mut ind, [1.0, 1.0]).iter().position(Option::is_some), Some(ind.warm_up() - 1));
+    }
+}
@@ -0,0 +1,41 @@
+//! Helpers for property tests comparing batch functions with their streaming
+//! indicators. Enabled with the `testing` feature, for dev-dependencies only.
+
+/// Small seeded generator (64-bit LCG), so property tests are reproducible
+/// without pulling in a random number crate.
+pub struct TestRng(u64);
+
+impl TestRng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    /// Uniform in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (self.0 >> 33) as f64 / (1u64 << 31) as f64
+    }
+
+    /// Uniform integer in `low..high`.
+    pub fn range(&mut self, low: usize, high: usize) -> usize {
+        low + (self.next_f64() * (high - low) as f64) as usize
+    }
+
+    /// Series of up to `max_len` values uniform in `[0, scale)`.
+    pub fn series(&mut self, max_len: usize, scale: f64) -> Vec<f64> {
+        let len = self.range(0, max_len);
+        (0..len).map(|_| self.next_f64() * scale).collect()
+    }
+}
+
+/// Assert two outputs line up: the same warm-up (`None`s) and values within `tolerance`.
+pub fn assert_outputs_close(batch: &[Option<f64>], streaming: &[Option<f64>], tolerance: f64) {
+    assert_eq!(batch.len(), streaming.len());
+    for (i, (b, s)) in batch.iter().zip(streaming).enumerate() {
+        match (b, s) {
+            (Some(b), Some(s)) => assert!((b - s).abs() < tolerance, "value mismatch at {i}: {b} vs {s}"),
+            (None, None) => {}
+            _ => panic!("warm-up mismatch at {i}: {batch:?} vs {streaming:?}"),
+        }
+    }
+}
@@ -20,6 +20,26 @@ pub fn moving_avg(data: &[u64], window: usize) -> Vec<f64> {
     result
 }
 
+/// Simple moving average of float prices, aligned with `data`: the first
+/// `window - 1` entries are `None`.
+pub fn moving_avg_f64(data: &[f64], window: usize) -> Vec<Option<f64>> {
+    let mut result = vec![None; data.len()];
+    if window == 0 || data.len() < window {
+        return result;
+    }
+
+    let mut sum: f64 = data[..window].iter().sum();
+    result[window - 1] = Some(sum / window as f64);
+
+    for i in window..data.len() {
+        sum += data[i] - data[i - window];
+        result[i] = Some(sum / window as f64);
+    }
+
+    result
+}
+
+#[derive(Debug, Clone)]
 pub struct Sma {
     pub window: usize,
     pub values: std::collections::VecDeque<f64>,
@@ -53,11 +73,39 @@ impl Sma {
             None
         }
     }
+
+    pub fn reset(&mut self) {
+        self.values.clear();
+        self.sum = 0.0;
+    }
+}
+
+impl indicator::Indicator for Sma {
+    type Input = f64;
+    type Output = f64;
+
+    fn next(&mut self, price: f64) -> Option<f64> {
+        Sma::next(self, price);
+        self.get()
+    }
+
+    fn value(&self) -> Option<f64> {
+        self.get()
+    }
+
+    fn reset(&mut self) {
+        Sma::reset(self)
+    }
+
+    fn warm_up(&self) -> usize {
+        self.window
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use indicator::testing::{assert_outputs_close, TestRng};
 
     #[test]
     fn it_works() {
@@ -109,4 +157,26 @@ mod tests {
         let expected = vec![5.0, 5.0, 5.0];
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_moving_avg_f64_small_prices() {
+        let data = vec![0.00042, 0.00044, 0.00046];
+        let result = moving_avg_f64(&data, 2);
+        assert_eq!(result[0], None);
+        assert!((result[1].unwrap() - 0.00043).abs() < 1e-12);
+        assert!((result[2].unwrap() - 0.00045).abs() < 1e-12);
+        assert_eq!(moving_avg_f64(&data, 0), vec![None, None, None]);
+    }
+
+    #[test]
+    fn test_moving_avg_f64_matches_streaming() {
+        // Property test over pseudo-random series, lengths and windows
+        let mut rng = TestRng::new(7);
+        for _ in 0..200 {
+            let window = rng.range(1, 13);
+            let data = rng.series(60, 1000.0);
+            let streaming = indicator::run(&mut Sma::new(window), data.iter().copied());
+            assert_outputs_close(&moving_avg_f64(&data, window), &streaming, 1e-9);
+        }
+    }
 }
@@ -47,6 +47,41 @@ pub fn log_return(data: &[u64]) -> Vec<f64> {
     returns_quant
 }
 
+/// Simple returns of float prices, aligned with `data`: the first entry is
+/// `None`, and a zero previous price gives `0.0`.
+pub fn simple_return_f64(data: &[f64]) -> Vec<Option<f64>> {
+    let mut returns_quant = Vec::with_capacity(data.len());
+    if data.is_empty() {
+        return returns_quant;
+    }
+
+    returns_quant.push(None);
+    for pair in data.windows(2) {
+        let (p, c) = (pair[0], pair[1]);
+        returns_quant.push(Some(if p != 0.0 { (c - p) / p } else { 0.0 }));
+    }
+
+    returns_quant
+}
+
+/// Log returns of float prices, aligned with `data`: the first entry is
+/// `None`, and non-positive prices give `0.0`.
+pub fn log_return_f64(data: &[f64]) -> Vec<Option<f64>> {
+    let mut returns_quant = Vec::with_capacity(data.len());
+    if data.is_empty() {
+        return returns_quant;
+    }
+
+    returns_quant.push(None);
+    for pair in data.windows(2) {
+        let (p, c) = (pair[0], pair[1]);
+        returns_quant.push(Some(if p > 0.0 && c > 0.0 { (c / p).ln() } else { 0.0 }));
+    }
+
+    returns_quant
+}
+
+#[derive(Debug, Clone)]
 pub struct Returns {
     pub prev: Option<f64>,
     pub last_return: Option<f64>,
@@ -71,12 +106,45 @@ impl Returns {
     pub fn get(&self) -> Option<f64> {
         self.last_return
     }
+
+    pub fn reset(&mut self) {
+        self.prev = None;
+        self.last_return = None;
+    }
+}
+
+impl Default for Returns {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
+impl indicator::Indicator for Returns {
+    type Input = f64;
+    type Output = f64;
+
+    fn next(&mut self, price: f64) -> Option<f64> {
+        Returns::next(self, price)
+    }
+
+    fn value(&self) -> Option<f64> {
+        self.last_return
+    }
+
+    fn reset(&mut self) {
+        Returns::reset(self)
+    }
+
+    /// The first return needs a previous price.
+    fn warm_up(&self) -> usize {
+        2
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use indicator::testing::{assert_outputs_close, TestRng};
 
     #[test]
     fn it_works() {
@@ -159,4 +227,40 @@ mod tests {
         let expected = vec![0.0]; // ln(100/100) = ln(1) = 0
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_returns_f64_small_prices() {
+        let data = vec![0.00040, 0.00042];
+        let simple = simple_return_f64(&data);
+        assert_eq!(simple[0], None);
+        assert!((simple[1].unwrap() - 0.05).abs() < 1e-12);
+        let log = log_return_f64(&data);
+        assert!((log[1].unwrap() - 1.05f64.ln()).abs() < 1e-12);
+        assert_eq!(simple_return_f64(&[]), vec![]);
+    }
+
+    #[test]
+    fn test_returns_f64_match_streaming() {
+        // Property test over pseudo-random series, including zero prices
+        let mut rng = TestRng::new(3);
+        for _ in 0..200 {
+            let data: Vec<f64> = rng.series(60, 10.0).into_iter().map(|p| if p < 0.5 { 0.0 } else { p }).collect();
+
+            let simple = simple_return_f64(&data);
+            assert_outputs_close(&simple, &indicator::run(&mut Returns::new(), data.iter().copied()), 1e-12);
+
+            let log = log_return_f64(&data);
+            assert_eq!(log.len(), data.len());
+            for (i, (l, s)) in log.iter().zip(&simple).enumerate() {
+                match (l, s) {
+                    (Some(l), Some(s)) if data[i - 1] > 0.0 && data[i] > 0.0 => {
+                        assert!((l - (1.0 + s).ln()).abs() < 1e-9)
+                    }
+                    (Some(l), Some(_)) => assert_eq!(*l, 0.0),
+                    (None, None) => assert_eq!(i, 0),
+                    _ => panic!("warm-up mismatch at {i}"),
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,255 @@
+//! Tail-risk and distribution statistics of a return series.
+//!
+//! Losses are reported as positive numbers: a 95% VaR of `0.02` means the
+//! return was (or is expected to be) worse than -2% on 5% of periods.
+
+use sharp_ratio::{average, std_deviation};
+
+/// Historical Value-at-Risk: the loss at the `1 - confidence` quantile of
+/// the observed returns.
+pub fn historical_var(returns: &[f64], confidence: f64) -> f64 {
+    match tail(returns, confidence) {
+        Some(tail) => -tail[tail.len() - 1],
+        None => 0.0,
+    }
+}
+
+/// Historical Expected Shortfall (CVaR): the average loss over the worst
+/// `1 - confidence` of the observed returns.
+pub fn historical_es(returns: &[f64], confidence: f64) -> f64 {
+    match tail(returns, confidence) {
+        Some(tail) => -average(&tail),
+        None => 0.0,
+    }
+}
+
+/// Value-at-Risk assuming normally distributed returns with the sample mean
+/// and standard deviation.
+pub fn parametric_var(returns: &[f64], confidence: f64) -> f64 {
+    if returns.is_empty() {
+        return 0.0;
+    }
+    let z = inverse_normal_cdf(confidence);
+    z * std_deviation(returns) - average(returns)
+}
+
+/// Expected Shortfall assuming normally distributed returns.
+pub fn parametric_es(returns: &[f64], confidence: f64) -> f64 {
+    if returns.is_empty() {
+        return 0.0;
+    }
+    let z = inverse_normal_cdf(confidence);
+    std_deviation(returns) * normal_pdf(z) / (1.0 - confidence) - average(returns)
+}
+
+/// Root mean square of the returns below `target` (returns above it count as zero).
+pub fn downside_deviation(returns: &[f64], target: f64) -> f64 {
+    if returns.is_empty() {
+        return 0.0;
+    }
+    let sum: f64 = returns.iter().map(|r| (r - target).min(0.0).powi(2)).sum();
+    (sum / returns.len() as f64).sqrt()
+}
+
+/// Skewness `m3 / m2^1.5` from the central moments; 0 for a symmetric
+/// distribution, negative when the left tail is longer.
+pub fn skewness(returns: &[f64]) -> f64 {
+    let (m2, m3, _) = central_moments(returns);
+    if m2 <= 1e-20 { 0.0 } else { m3 / m2.powf(1.5) }
+}
+
+/// Excess kurtosis `m4 / m2^2 - 3`; 0 for a normal distribution, positive
+/// for fat tails.
+pub fn excess_kurtosis(returns: &[f64]) -> f64 {
+    let (m2, _, m4) = central_moments(returns);
+    if m2 <= 1e-20 { 0.0 } else { m4 / (m2 * m2) - 3.0 }
+}
+
+/// Apply `statistic` over a sliding window, aligned with `data`: the first
+/// `window - 1` entries are `None`. E.g. a 30-period rolling VaR is
+/// `rolling(&returns, 30, |w| historical_var(w, 0.95))`.
+pub fn rolling<F>(data: &[f64], window: usize, statistic: F) -> Vec<Option<f64>>
+where
+    F: Fn(&[f64]) -> f64,
+{
+    let mut result = vec![None; data.len()];
+    if window == 0 {
+        return result;
+    }
+    for (i, slice) in data.windows(window).enumerate() {
+        result[i + window - 1] = Some(statistic(slice));
+    }
+    result
+}
+
+/// Standard normal density.
+pub fn normal_pdf(x: f64) -> f64 {
+    (-0.5 * x * x).exp() / (2.0 * std::f64::consts::PI).sqrt()
+}
+
+/// Standard normal CDF, via the complementary error function approximation
+/// from Numerical Recipes (`erfcc`, fractional error below 1.2e-7).
+pub fn normal_cdf(x: f64) -> f64 {
+    let z = x.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let poly = -z * z - 1.26551223
+        + t * (1.00002368
+            + t * (0.37409196
+                + t * (0.09678418
+                    + t * (-0.18628806
+                        + t * (0.27886807 + t * (-1.13520398 + t * (1.48851587 + t * (-0.82215223 + t * 0.17087277))))))));
+    let tail = 0.5 * t * poly.exp();
+    if x >= 0.0 { 1.0 - tail } else { tail }
+}
+
+/// Inverse of the standard normal CDF (Acklam's rational approximation,
+/// relative error below 1.2e-9). Returns infinities outside `(0, 1)`.
+pub fn inverse_normal_cdf(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e1,
+        2.209460984245205e2,
+        -2.759285104469687e2,
+        1.38357751867269e2,
+        -3.066479806614716e1,
+        2.506628277459239,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e1,
+        1.615858368580409e2,
+        -1.556989798598866e2,
+        6.680131188771972e1,
+        -1.328068155288572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-3,
+        -3.223964580411365e-1,
+        -2.400758277161838,
+        -2.549732539343734,
+        4.374664141464968,
+        2.938163982698783,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-3,
+        3.224671290700398e-1,
+        2.445134137142996,
+        3.754408661907416,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    if p <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p >= 1.0 {
+        return f64::INFINITY;
+    }
+
+    if p < P_LOW {
+        let q = (-2.0 * p.ln()).sqrt();
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    } else if p <= 1.0 - P_LOW {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        -inverse_normal_cdf(1.0 - p)
+    }
+}
+
+/// The worst `ceil((1 - confidence) * n)` returns (at least one), sorted ascending.
+fn tail(returns: &[f64], confidence: f64) -> Option<Vec<f64>> {
+    if returns.is_empty() {
+        return None;
+    }
+    let mut sorted = returns.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let count = (((1.0 - confidence) * sorted.len() as f64).ceil() as usize).clamp(1, sorted.len());
+    sorted.truncate(count);
+    Some(sorted)
+}
+
+/// Second, third and fourth central moments (population, divided by n).
+fn central_moments(data: &[f64]) -> (f64, f64, f64) {
+    if data.is_empty() {
+        return (0.0, 0.0, 0.0);
+    }
+    let mean = average(data);
+    let n = data.len() as f64;
+    let (mut m2, mut m3, mut m4) = (0.0, 0.0, 0.0);
+    for value in data {
+        let d = value - mean;
+        m2 += d * d;
+        m3 += d * d * d;
+        m4 += d * d * d * d;
+    }
+    (m2 / n, m3 / n, m4 / n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inverse_normal_cdf() {
+        assert!(inverse_normal_cdf(0.5).abs() < 1e-9);
+        assert!((inverse_normal_cdf(0.95) - 1.6448536).abs() < 1e-6);
+        assert!((inverse_normal_cdf(0.01) + 2.3263479).abs() < 1e-6);
+        assert_eq!(inverse_normal_cdf(0.0), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_normal_cdf() {
+        assert!((normal_cdf(0.0) - 0.5).abs() < 1e-7);
+        assert!((normal_cdf(1.6448536) - 0.95).abs() < 1e-6);
+        assert!((normal_cdf(-2.3263479) - 0.01).abs() < 1e-6);
+        for p in [0.001, 0.2, 0.7, 0.999] {
+            assert!((normal_cdf(inverse_normal_cdf(p)) - p).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_historical_var_and_es() {
+        // Worst 10% of 20 returns are the two smallest: -0.05 and -0.03
+        let mut returns = vec![0.01; 18];
+        returns.push(-0.05);
+        returns.push(-0.03);
+        assert!((historical_var(&returns, 0.9) - 0.03).abs() < 1e-12);
+        assert!((historical_es(&returns, 0.9) - 0.04).abs() < 1e-12);
+        assert_eq!(historical_var(&[], 0.95), 0.0);
+    }
+
+    #[test]
+    fn test_parametric_var_and_es() {
+        let returns = [-0.02, -0.01, 0.0, 0.01, 0.02];
+        let std = std_deviation(&returns);
+        assert!((parametric_var(&returns, 0.95) - 1.6448536 * std).abs() < 1e-6);
+        // ES is always beyond VaR
+        assert!(parametric_es(&returns, 0.95) > parametric_var(&returns, 0.95));
+    }
+
+    #[test]
+    fn test_moments_and_downside() {
+        let symmetric = [-2.0, -1.0, 0.0, 1.0, 2.0];
+        assert!(skewness(&symmetric).abs() < 1e-12);
+        // m2 = 2, m4 = 6.8 -> 6.8 / 4 - 3
+        assert!((excess_kurtosis(&symmetric) + 1.3).abs() < 1e-12);
+        assert!(skewness(&[0.0, 0.0, 0.0, 10.0]) > 0.0);
+        assert_eq!(skewness(&[1.0, 1.0]), 0.0);
+
+        // Only -2 and -1 count: sqrt((4 + 1) / 5)
+        assert!((downside_deviation(&symmetric, 0.0) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_rolling_matches_direct() {
+        let data = [0.01, -0.02, 0.03, -0.04, 0.05];
+        let rolled = rolling(&data, 3, skewness);
+        assert_eq!(rolled[..2], [None, None]);
+        for i in 2..data.len() {
+            assert_eq!(rolled[i], Some(skewness(&data[i - 2..=i])));
+        }
+        assert_eq!(rolling(&data, 0, skewness), vec![None; 5]);
+        assert_eq!(rolling(&data, 9, skewness), vec![None; 5]);
+    }
+}
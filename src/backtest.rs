@@ -1,10 +1,43 @@
-use crate::types::{Candle, Trade, EquitySnapshot};
+use crate::types::{Candle, Trade, EquitySnapshot, DiagnosticsSnapshot, MarginCall};
 use crate::strategy::Strategy;
 use crate::trade_model::TradeModel;
 
 pub struct BacktestResult {
     pub trades: Vec<Trade>,
     pub equity_curve: Vec<EquitySnapshot>,
+    pub diagnostics: Vec<DiagnosticsSnapshot>,
+    pub margin_calls: Vec<MarginCall>,
+    /// Timestamp of the candle where the account was wiped out and the run
+    /// stopped; `None` if it ran through all candles.
+    pub terminated: Option<i64>,
+}
+
+impl BacktestResult {
+    /// Names of all recorded diagnostics, in order of first appearance.
+    pub fn diagnostic_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for snap in &self.diagnostics {
+            for (name, _) in &snap.values {
+                if !names.contains(name) {
+                    names.push(name.clone());
+                }
+            }
+        }
+        names
+    }
+
+    /// `(timestamp, value)` pairs for one diagnostic, skipping candles where it was not reported.
+    pub fn diagnostic_series(&self, name: &str) -> Vec<(i64, f64)> {
+        self.diagnostics
+            .iter()
+            .filter_map(|snap| {
+                snap.values
+                    .iter()
+                    .find(|(n, _)| n == name)
+                    .map(|&(_, value)| (snap.timestamp, value))
+            })
+            .collect()
+    }
 }
 
 pub fn backtest(
@@ -14,12 +47,25 @@ pub fn backtest(
 ) -> BacktestResult {
     let mut trades = Vec::new();
     let mut equity_curve = Vec::new();
+    let mut diagnostics = Vec::new();
+    let mut margin_calls = Vec::new();
+    let mut terminated = None;
 
     for candle in candles {
+        if let Some(trade) = trade_model.settle(candle) {
+            trades.push(trade);
+        }
         let signal = strategy.next(candle);
+        diagnostics.push(DiagnosticsSnapshot {
+            timestamp: candle.timestamp,
+            values: strategy.diagnostics(),
+        });
         if let Some(trade) = trade_model.apply(signal, candle) {
             trades.push(trade);
         }
+        if let Some(call) = trade_model.check_margin(candle) {
+            margin_calls.push(call);
+        }
         let equity = trade_model.equity(candle.close);
         equity_curve.push(EquitySnapshot {
             timestamp: candle.timestamp,
@@ -27,8 +73,57 @@ pub fn backtest(
             equity,
             position_value: equity - trade_model.cash,
             drawdown: 0.0, // to be computed in metrics
+            realized_pnl: trade_model.realized_pnl,
+            unrealized_pnl: trade_model.unrealized_pnl(candle.close),
+            fees: trade_model.fees_paid,
+            slippage: trade_model.slippage_cost,
         });
+        if trade_model.is_wiped_out() {
+            terminated = Some(candle.timestamp);
+            break;
+        }
     }
 
-    BacktestResult { trades, equity_curve }
-}
\ No newline at end of file
+    BacktestResult { trades, equity_curve, diagnostics, margin_calls, terminated }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instrument::{Instrument, Perpetual};
+    use crate::types::Signal;
+
+    #[derive(Clone)]
+    struct AlwaysBuy;
+
+    impl Strategy for AlwaysBuy {
+        fn next(&mut self, _candle: &Candle) -> Signal {
+            Signal::Buy
+        }
+        fn reset(&mut self) {}
+        fn box_clone(&self) -> Box<dyn Strategy> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn test_run_stops_when_wiped_out() {
+        let candles: Vec<Candle> = [100.0, 96.0, 92.0, 85.0, 80.0, 120.0]
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| Candle { timestamp: i as i64 * 60, open: close, high: close, low: close, close, volume: 1.0, period: 1.0 })
+            .collect();
+        let mut model = TradeModel::new(100.0, 0.0, 0.0, 1.0)
+            .with_instrument(Instrument::Perpetual(Perpetual::new(10.0, 0.1, 0.0)))
+            .with_margin_call(0.05);
+        let result = backtest(&candles, &mut AlwaysBuy, &mut model);
+
+        // Margin call once the 10x long is down to 20 of collateral, then the
+        // candle at 85 gaps through the liquidation price of 90
+        assert_eq!(result.margin_calls.len(), 1);
+        assert_eq!(result.margin_calls[0].timestamp, 120);
+        assert_eq!(result.trades.len(), 2);
+        assert!(result.trades[1].liquidation);
+        assert_eq!(result.terminated, Some(180));
+        assert_eq!(result.equity_curve.len(), 4);
+    }
+}
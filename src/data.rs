@@ -1,11 +1,10 @@
 // src/data.rs
 
 use std::fs;
-use std::path::Path;
 use std::error::Error;
 use std::collections::HashMap;
-use serde::Deserialize;
 
+use crate::instrument::FundingRate;
 use crate::types::Candle;
 
 pub fn load_token_csvs(
@@ -41,3 +40,18 @@ pub fn load_token_csvs(
 
     Ok(result)
 }
+
+/// Load perpetual funding rates from a headerless `timestamp,rate` CSV.
+pub fn load_funding_rates(path: &str) -> Result<Vec<FundingRate>, Box<dyn Error>> {
+    let file = fs::File::open(path)?;
+    let mut rdr = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .from_reader(file);
+
+    let mut rates = Vec::new();
+    for result in rdr.deserialize() {
+        let record: FundingRate = result?;
+        rates.push(record);
+    }
+    Ok(rates)
+}
@@ -0,0 +1,107 @@
+// src/export.rs
+
+use std::error::Error;
+use std::path::Path;
+
+use crate::backtest::BacktestResult;
+use crate::metrics::RollingMetrics;
+use crate::types::Signal;
+
+/// Write the equity curve to CSV, one row per candle, followed by one column
+/// per strategy diagnostic. Cells are left empty while a diagnostic is not
+/// reported (e.g. during indicator warm-up).
+pub fn write_equity_csv<P: AsRef<Path>>(path: P, result: &BacktestResult) -> Result<(), Box<dyn Error>> {
+    let names = result.diagnostic_names();
+    let mut wtr = csv::Writer::from_path(path)?;
+
+    let mut header = vec![
+        "timestamp".to_string(),
+        "cash".to_string(),
+        "equity".to_string(),
+        "position_value".to_string(),
+        "drawdown".to_string(),
+        "realized_pnl".to_string(),
+        "unrealized_pnl".to_string(),
+        "fees".to_string(),
+        "slippage".to_string(),
+    ];
+    header.extend(names.iter().cloned());
+    wtr.write_record(&header)?;
+
+    for (i, snap) in result.equity_curve.iter().enumerate() {
+        let mut record = vec![
+            snap.timestamp.to_string(),
+            snap.cash.to_string(),
+            snap.equity.to_string(),
+            snap.position_value.to_string(),
+            snap.drawdown.to_string(),
+            snap.realized_pnl.to_string(),
+            snap.unrealized_pnl.to_string(),
+            snap.fees.to_string(),
+            snap.slippage.to_string(),
+        ];
+        let values = result.diagnostics.get(i).map(|d| d.values.as_slice()).unwrap_or(&[]);
+        for name in &names {
+            let cell = values
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, v)| v.to_string())
+                .unwrap_or_default();
+            record.push(cell);
+        }
+        wtr.write_record(&record)?;
+    }
+
+    wtr.flush()?;
+    Ok(())
+}
+
+/// Write rolling-window metrics to CSV, one row per equity snapshot. Cells
+/// are left empty until the window is full.
+pub fn write_rolling_metrics_csv<P: AsRef<Path>>(path: P, rolling: &RollingMetrics) -> Result<(), Box<dyn Error>> {
+    let mut wtr = csv::Writer::from_path(path)?;
+    wtr.write_record(["timestamp", "window", "return", "volatility", "sharpe_ratio", "max_drawdown"])?;
+
+    let cell = |value: Option<f64>| value.map(|v| v.to_string()).unwrap_or_default();
+    for (i, timestamp) in rolling.timestamps.iter().enumerate() {
+        wtr.write_record([
+            timestamp.to_string(),
+            rolling.window.to_string(),
+            cell(rolling.returns[i]),
+            cell(rolling.volatility[i]),
+            cell(rolling.sharpe_ratio[i]),
+            cell(rolling.max_drawdown[i]),
+        ])?;
+    }
+
+    wtr.flush()?;
+    Ok(())
+}
+
+/// Write the trade log to CSV.
+pub fn write_trades_csv<P: AsRef<Path>>(path: P, result: &BacktestResult) -> Result<(), Box<dyn Error>> {
+    let mut wtr = csv::Writer::from_path(path)?;
+    wtr.write_record(["timestamp", "action", "price", "size", "fee", "liquidation", "remaining", "realized_pnl", "slippage"])?;
+
+    for trade in &result.trades {
+        let action = match trade.action {
+            Signal::Buy => "buy",
+            Signal::Sell => "sell",
+            Signal::Hold => "hold",
+        };
+        wtr.write_record([
+            trade.timestamp.to_string(),
+            action.to_string(),
+            trade.price.to_string(),
+            trade.size.to_string(),
+            trade.fee.to_string(),
+            trade.liquidation.to_string(),
+            trade.remaining.to_string(),
+            trade.realized_pnl.to_string(),
+            trade.slippage.to_string(),
+        ])?;
+    }
+
+    wtr.flush()?;
+    Ok(())
+}
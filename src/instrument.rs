@@ -0,0 +1,124 @@
+// src/instrument.rs
+//
+// What the trade model is trading: spot (long only, fully paid) or a
+// perpetual future (long or short on margin, with funding payments).
+
+use serde::Deserialize;
+
+/// Funding rate that applies at `timestamp`, as a fraction of the position
+/// notional. Positive rates are paid by longs to shorts.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct FundingRate {
+    pub timestamp: i64,
+    pub rate: f64,
+}
+
+/// Perpetual future settings. Margin rates are fractions of the notional.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Perpetual {
+    pub leverage: f64,
+    /// Minimum margin to open a position, e.g. `0.1` allows up to 10x.
+    pub initial_margin: f64,
+    /// Margin below which the position is liquidated, e.g. `0.005`.
+    pub maintenance_margin: f64,
+    /// Funding rates sorted by timestamp.
+    pub funding: Vec<FundingRate>,
+}
+
+impl Perpetual {
+    pub fn new(leverage: f64, initial_margin: f64, maintenance_margin: f64) -> Self {
+        Self {
+            leverage: leverage.max(1.0),
+            initial_margin,
+            maintenance_margin,
+            funding: Vec::new(),
+        }
+    }
+
+    pub fn with_funding(mut self, mut funding: Vec<FundingRate>) -> Self {
+        funding.sort_by_key(|f| f.timestamp);
+        self.funding = funding;
+        self
+    }
+
+    /// Leverage actually used: the requested one, capped by the initial margin.
+    pub fn effective_leverage(&self) -> f64 {
+        if self.initial_margin > 0.0 {
+            self.leverage.min(1.0 / self.initial_margin)
+        } else {
+            self.leverage
+        }
+    }
+
+    /// Sum of the funding rates with `after < timestamp <= until`.
+    pub fn funding_between(&self, after: i64, until: i64) -> f64 {
+        let start = self.funding.partition_point(|f| f.timestamp <= after);
+        self.funding[start..]
+            .iter()
+            .take_while(|f| f.timestamp <= until)
+            .map(|f| f.rate)
+            .sum()
+    }
+
+    /// Price at which the account equity (`collateral` plus unrealized P&L)
+    /// falls to the maintenance margin of the position.
+    pub fn liquidation_price(&self, collateral: f64, entry_price: f64, size: f64, is_long: bool) -> f64 {
+        liquidation_price(collateral, entry_price, size, is_long, self.maintenance_margin)
+    }
+}
+
+/// Price at which `collateral` plus the unrealized P&L of a position falls to
+/// `maintenance_margin` times its notional. Longs that cannot be wiped out get 0.
+pub fn liquidation_price(collateral: f64, entry_price: f64, size: f64, is_long: bool, maintenance_margin: f64) -> f64 {
+    if size <= 0.0 {
+        return if is_long { 0.0 } else { f64::INFINITY };
+    }
+    let mm = maintenance_margin;
+    if is_long {
+        // collateral + size * (p - entry) = mm * size * p
+        ((size * entry_price - collateral) / (size * (1.0 - mm))).max(0.0)
+    } else {
+        // collateral + size * (entry - p) = mm * size * p
+        (collateral + size * entry_price) / (size * (1.0 + mm))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Instrument {
+    /// Long only, the whole position paid in cash.
+    Spot,
+    Perpetual(Perpetual),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_liquidation_price() {
+        let perp = Perpetual::new(10.0, 0.1, 0.0);
+        // 100 collateral, 10x long of 10 units at 100: wiped out at 90 without maintenance margin
+        assert!((perp.liquidation_price(100.0, 100.0, 10.0, true) - 90.0).abs() < 1e-9);
+        assert!((perp.liquidation_price(100.0, 100.0, 10.0, false) - 110.0).abs() < 1e-9);
+
+        let perp = Perpetual::new(10.0, 0.1, 0.01);
+        let price = perp.liquidation_price(100.0, 100.0, 10.0, true);
+        let equity = 100.0 + 10.0 * (price - 100.0);
+        assert!((equity - 0.01 * 10.0 * price).abs() < 1e-9);
+        // Unleveraged longs cannot be liquidated
+        assert_eq!(perp.liquidation_price(1000.0, 100.0, 10.0, true), 0.0);
+    }
+
+    #[test]
+    fn test_funding_between_and_leverage_cap() {
+        let perp = Perpetual::new(50.0, 0.05, 0.01).with_funding(vec![
+            FundingRate { timestamp: 300, rate: 0.0003 },
+            FundingRate { timestamp: 100, rate: 0.0001 },
+            FundingRate { timestamp: 200, rate: -0.0002 },
+        ]);
+        assert!((perp.funding_between(0, 200) + 0.0001).abs() < 1e-12);
+        assert!((perp.funding_between(100, 300) - 0.0001).abs() < 1e-12);
+        assert_eq!(perp.funding_between(300, 1000), 0.0);
+        assert_eq!(perp.effective_leverage(), 20.0);
+    }
+}
@@ -4,6 +4,17 @@ pub mod data;
 pub mod backtest;
 pub mod trade_model;
 pub mod metrics;
+pub mod rolling;
+pub mod report;
+pub mod export;
+pub mod terminal;
+pub mod rng;
+pub mod monte_carlo;
+pub mod synthetic;
+pub mod optimize;
+pub mod periodic;
+pub mod instrument;
+pub mod sizing;
 
 pub use types::*;
 pub use strategy::*;
\ No newline at end of file
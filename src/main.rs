@@ -1,17 +1,29 @@
+use std::fs;
 use std::io;
 
-use crate::data::load_token_csvs;
-use strategy::{EmaCross, SmaCross, MeanReversion, Momentum, ATRBreakout, VolatilityTargeting, Strategy};
-use backtest::{backtest, BacktestResult};
-use trade_model::TradeModel;
-use metrics::compute_metrics;
+use mini_backtester::data::load_token_csvs;
+use mini_backtester::synthetic::preset_dataset;
+use mini_backtester::strategy::{ParamValue, Params, Registry};
+use mini_backtester::backtest::backtest;
+use mini_backtester::trade_model::TradeModel;
+use mini_backtester::metrics::{compute_metrics, compute_rolling_metrics};
+use mini_backtester::monte_carlo::MonteCarlo;
+use mini_backtester::optimize::{param_grid, sweep, PurgedCv};
+use mini_backtester::periodic::{monthly_table, period_returns, period_stats, Period};
+use mini_backtester::report::write_html_report;
+use mini_backtester::export::{write_equity_csv, write_rolling_metrics_csv, write_trades_csv};
+use mini_backtester::terminal::{format_number, line_chart, sparkline, Align, Table};
 
-mod types;
-mod data;
-mod strategy;
-mod trade_model;
-mod backtest;
-mod metrics;
+/// Candles per window for the rolling metrics export.
+const ROLLING_WINDOW: usize = 20;
+/// Runs and seed of the Monte Carlo bootstrap printed per strategy.
+const MONTE_CARLO_RUNS: usize = 1000;
+const MONTE_CARLO_SEED: u64 = 42;
+/// Candles per series and seed when running on synthetic data.
+const SYNTHETIC_CANDLES: usize = 2_000;
+const SYNTHETIC_SEED: u64 = 7;
+/// Candles used for the EMA Cross parameter sweep of each file.
+const SWEEP_CANDLES: usize = 5_000;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     // EXAMPLE CODE FOR MATH LIBS
@@ -72,72 +84,92 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("------------ MINI BACKTESTER ------------");
 
     let mut input = String::new();
-    println!("Enter token name (e.g. ETCUSD): ");
+    println!("Enter token name (e.g. ETCUSD, or \"synthetic\" for generated data): ");
     io::stdin().read_line(&mut input)?;
     let token = input.trim();
 
-    let files = load_token_csvs("Kraken_OHLCVT_Q4_2024/", token)?;
+    let files = if token.eq_ignore_ascii_case("synthetic") {
+        preset_dataset(SYNTHETIC_CANDLES, SYNTHETIC_SEED)
+    } else {
+        load_token_csvs("Kraken_OHLCVT_Q4_2024/", token)?
+    };
 
     println!("Found files: ");
     for (filename, candles) in &files {
         println!("- {} ({} candles)", filename, candles.len());
     }
 
-    let mut strategies: Vec<Box<dyn Strategy>> = vec![
-        Box::new(EmaCross::new(9, 21)),
-        Box::new(SmaCross::new(10, 30)),
-        Box::new(MeanReversion::new(0.01)),
-        Box::new(Momentum::new(0.01)),
-        Box::new(ATRBreakout::new(10, 14, 0.01)),
+    let registry = Registry::builtin();
+    let int = |name: &str, v: i64| (name.to_string(), ParamValue::Int(v));
+    let float = |name: &str, v: f64| (name.to_string(), ParamValue::Float(v));
+    let configs: Vec<(&str, Params)> = vec![
+        ("ema_cross", Params::from([int("short_period", 9), int("long_period", 21)])),
+        ("sma_cross", Params::from([int("short_period", 10), int("long_period", 30)])),
+        ("mean_reversion", Params::from([float("threshold", 0.01)])),
+        ("momentum", Params::from([float("threshold", 0.01)])),
+        ("atr_breakout", Params::from([int("lookback", 10), int("atr_period", 14), float("atr_multiplier", 0.01)])),
+        ("volume_breakout", Params::from([int("lookback", 10), int("atr_period", 14), float("atr_multiplier", 0.01), int("volume_period", 20), float("volume_multiplier", 1.5)])),
+        ("turtle", Params::from([int("entry_period", 20), int("exit_period", 10), int("atr_period", 20), float("risk_per_trade", 0.01), float("stop_atr", 2.0)])),
+        ("rsi_reversion", Params::from([int("period", 14), float("oversold", 30.0), float("overbought", 70.0)])),
+        ("macd_cross", Params::from([int("fast_period", 12), int("slow_period", 26), int("signal_period", 9)])),
+        ("bollinger_reversion", Params::from([int("period", 20), float("num_std", 2.0)])),
         // Volatility Targeting with EMA Cross as base strategy
-        Box::new(VolatilityTargeting::new(
-            0.01,  // 1% target daily volatility
-            20,    // 20-day lookback for volatility calculation
-            Box::new(EmaCross::new(9, 21)), // Base strategy for signals
-            0.1,   // Minimum position multiplier (10% of normal size)
-            3.0,   // Maximum position multiplier (300% of normal size)
-        )),
+        ("volatility_targeting", Params::from([
+            float("target_volatility", 0.01), // 1% target daily volatility
+            int("lookback", 20),              // 20-day lookback for volatility calculation
+            float("min_multiplier", 0.1),     // Minimum position multiplier (10% of normal size)
+            float("max_multiplier", 3.0),     // Maximum position multiplier (300% of normal size)
+        ])),
     ];
+    let labels = configs
+        .iter()
+        .map(|(name, params)| registry.label(name, params))
+        .collect::<Result<Vec<_>, _>>()?;
+    let prototypes = configs
+        .iter()
+        .map(|(name, params)| registry.create(name, params))
+        .collect::<Result<Vec<_>, _>>()?;
 
-    println!("Available strategies: {}", strategies.len());
+    println!("Available strategies: {}", configs.len());
+
+    let periods = |values: &[i64]| values.iter().map(|&v| ParamValue::Int(v)).collect::<Vec<_>>();
+    let sweep_grid = param_grid(&[
+        ("short_period", periods(&[5, 9, 12, 20])),
+        ("long_period", periods(&[21, 30, 50, 100])),
+    ]);
+
+    let report_dir = "reports";
+    fs::create_dir_all(report_dir)?;
 
     // 🔁 NEW: loop through each file
     for (filename, candles) in &files {
         println!("\n--- Running strategies on file: {} ---", filename);
 
-        // Reset each strategy to a new instance for this file
-        let mut fresh_strategies: Vec<Box<dyn Strategy>> = vec![
-            Box::new(EmaCross::new(9, 21)),
-            Box::new(SmaCross::new(10, 30)),
-            Box::new(MeanReversion::new(0.01)),
-            Box::new(Momentum::new(0.01)),
-            Box::new(ATRBreakout::new(10, 14, 0.01)),
-            // Volatility Targeting with EMA Cross as base strategy
-            Box::new(VolatilityTargeting::new(
-                0.01,  // 1% target daily volatility
-                20,    // 20-day lookback for volatility calculation
-                Box::new(EmaCross::new(9, 21)), // Base strategy for signals
-                0.1,   // Minimum position multiplier (10% of normal size)
-                3.0,   // Maximum position multiplier (300% of normal size)
-            )),
-        ];
+        // Start every file from clean copies of the configured prototypes
+        let mut fresh_strategies = prototypes.clone();
+
+        let mut summary = Table::new(&[
+            ("#", Align::Right),
+            ("Strategy", Align::Left),
+            ("Trades", Align::Right),
+            ("Final equity", Align::Right),
+            ("P&L", Align::Right),
+            ("Sharpe", Align::Right),
+            ("Max DD %", Align::Right),
+            ("Equity", Align::Left),
+        ]);
+        let mut best: Option<(&str, Vec<f64>, String)> = None;
 
         for (i, strat) in fresh_strategies.iter_mut().enumerate() {
-            let strategy_name = match i {
-                0 => "EMA Cross (9,21)",
-                1 => "SMA Cross (10,30)", 
-                2 => "Mean Reversion",
-                3 => "Momentum",
-                4 => "ATR Breakout",
-                5 => "Volatility Targeting + EMA Cross",
-                _ => "Unknown Strategy",
-            };
-            
+            let strategy_name = labels[i].as_str();
+
             println!("\nStrategy {}: {}", i + 1, strategy_name);
             
             // Step 7: Run a full backtest on a small sample
             let mut trade_model = TradeModel::new(1000.0, 0.001, 0.001, 1.0);
-            let result = backtest(&candles[..candles.len().min(100)], strat.as_mut(), &mut trade_model);
+            trade_model.sizer = strat.sizer();
+            let sample = &candles[..candles.len().min(100)];
+            let result = backtest(sample, strat.as_mut(), &mut trade_model);
             let metrics = compute_metrics(&result.equity_curve);
             
             println!("Total trades: {}", result.trades.len());
@@ -145,17 +177,129 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             println!("Sharpe ratio: {:.4}", metrics.sharpe_ratio);
             println!("Max drawdown: {:.2}%", metrics.max_drawdown * 100.0);
             println!("Total P&L: {:.2}", metrics.total_pnl);
+            if let Some(last) = result.equity_curve.last() {
+                println!(
+                    "Realized P&L: {:.2}, unrealized: {:.2}, fees: {:.2}, slippage: {:.2}",
+                    last.realized_pnl, last.unrealized_pnl, last.fees, last.slippage,
+                );
+            }
+            if !result.margin_calls.is_empty() {
+                println!("Margin calls: {}", result.margin_calls.len());
+            }
+            if let Some(timestamp) = result.terminated {
+                println!("Account wiped out at {}, run stopped", timestamp);
+            }
+            println!(
+                "VaR 95%: {:.3}% (parametric {:.3}%), ES 95%: {:.3}%, skew {:.3}, excess kurtosis {:.3}",
+                metrics.value_at_risk * 100.0,
+                metrics.parametric_var * 100.0,
+                metrics.expected_shortfall * 100.0,
+                metrics.skewness,
+                metrics.excess_kurtosis,
+            );
+
+            let daily = period_stats(&period_returns(&result.equity_curve, Period::Day));
+            if let (Some(best_day), Some(worst_day)) = (&daily.best, &daily.worst) {
+                println!(
+                    "Daily returns (UTC): {:.0}% positive of {}, best {:.2}% ({}), worst {:.2}% ({})",
+                    daily.positive_share * 100.0,
+                    daily.count,
+                    best_day.value * 100.0,
+                    best_day.label,
+                    worst_day.value * 100.0,
+                    worst_day.label,
+                );
+            }
+
+            // How much of the result could be luck: 90% intervals over bootstrapped equity paths
+            let mc = MonteCarlo::new(MONTE_CARLO_RUNS, MONTE_CARLO_SEED).with_block_size(5).equity_returns(&result);
+            let (equity_lo, equity_hi) = mc.final_equity.confidence_interval(0.9);
+            let (dd_lo, dd_hi) = mc.max_drawdown.confidence_interval(0.9);
+            let (sharpe_lo, sharpe_hi) = mc.sharpe_ratio.confidence_interval(0.9);
+            println!(
+                "Monte Carlo 90%: final equity {:.2}..{:.2}, max drawdown {:.2}%..{:.2}%, Sharpe {:.4}..{:.4}",
+                equity_lo,
+                equity_hi,
+                dd_lo * 100.0,
+                dd_hi * 100.0,
+                sharpe_lo,
+                sharpe_hi,
+            );
+
+            let equity: Vec<f64> = result.equity_curve.iter().map(|e| e.equity).collect();
+            let final_equity = equity.last().copied().unwrap_or(0.0);
+            summary.add_row(vec![
+                (i + 1).to_string(),
+                strategy_name.to_string(),
+                result.trades.len().to_string(),
+                format_number(final_equity, 2),
+                format_number(metrics.total_pnl, 2),
+                format!("{:.4}", metrics.sharpe_ratio),
+                format!("{:.2}", metrics.max_drawdown * 100.0),
+                sparkline(&equity, 30),
+            ]);
+            if best.as_ref().is_none_or(|(_, b, _)| b.last().copied().unwrap_or(0.0) < final_equity) {
+                best = Some((strategy_name, equity, monthly_table(&result.equity_curve)));
+            }
+
+            let report_path = format!("{}/{}_strategy{}.html", report_dir, filename.trim_end_matches(".csv"), i + 1);
+            write_html_report(&report_path, &format!("{} - {}", filename, strategy_name), sample, &result, &metrics)?;
+            println!("Report: {}", report_path);
             
-            // Special output for volatility targeting strategy
-            if i == 5 {
-                if let Some(vol_strat) = strat.as_any().downcast_ref::<VolatilityTargeting>() {
-                    println!("Current position multiplier: {:.2}x", vol_strat.get_position_multiplier());
-                    println!("Current realized volatility: {:.4} ({:.2}%)", 
-                        vol_strat.get_realized_volatility(), 
-                        vol_strat.get_realized_volatility() * 100.0);
-                }
+            let equity_path = format!("{}/{}_strategy{}_equity.csv", report_dir, filename.trim_end_matches(".csv"), i + 1);
+            write_equity_csv(&equity_path, &result)?;
+            write_trades_csv(equity_path.replace("_equity.csv", "_trades.csv"), &result)?;
+            let rolling = compute_rolling_metrics(&result.equity_curve, ROLLING_WINDOW);
+            write_rolling_metrics_csv(equity_path.replace("_equity.csv", "_rolling.csv"), &rolling)?;
+
+            // Latest indicator values / internal state of the strategy
+            for (name, value) in strat.diagnostics() {
+                println!("  {}: {:.4}", name, value);
             }
         }
+
+        println!("\n=== Summary: {} ===", filename);
+        print!("{}", summary.render());
+
+        if let Some((name, equity, monthly)) = &best {
+            println!("\nEquity curve of best strategy ({}):", name);
+            print!("{}", line_chart(equity, 60, 10));
+            println!("Monthly returns (%):");
+            print!("{}", monthly);
+        }
+
+        // Parameter sweep: raw Sharpe next to its deflated / cross-validated versions
+        let sweep_candles = &candles[..candles.len().min(SWEEP_CANDLES)];
+        let results = sweep(&registry, "ema_cross", &sweep_grid, sweep_candles, || TradeModel::new(1000.0, 0.001, 0.001, 1.0))?;
+        let mut table = Table::new(&[
+            ("Configuration", Align::Left),
+            ("Sharpe", Align::Right),
+            ("PSR", Align::Right),
+            ("DSR", Align::Right),
+            ("P&L", Align::Right),
+            ("Max DD %", Align::Right),
+        ]);
+        for result in results.iter().take(5) {
+            table.add_row(vec![
+                result.label.clone(),
+                format!("{:.4}", result.metrics.sharpe_ratio),
+                format!("{:.3}", result.metrics.probabilistic_sharpe),
+                format!("{:.3}", result.deflated_sharpe),
+                format_number(result.metrics.total_pnl, 2),
+                format!("{:.2}", result.metrics.max_drawdown * 100.0),
+            ]);
+        }
+        println!("\n=== EMA Cross sweep: top 5 of {} ({} candles) ===", results.len(), sweep_candles.len());
+        print!("{}", table.render());
+
+        let cv = PurgedCv::new(6, 2, 50).run(&results);
+        println!(
+            "CPCV ({} splits): mean Sharpe in-sample {:.4}, out-of-sample {:.4}, probability of overfitting {:.0}%",
+            cv.splits.len(),
+            cv.mean_in_sample_sharpe,
+            cv.mean_out_of_sample_sharpe,
+            cv.probability_of_overfitting * 100.0,
+        );
     }
 
 
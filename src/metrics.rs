@@ -1,21 +1,43 @@
 use crate::types::EquitySnapshot;
-use return_quant;
-use sharp_ratio;
+
+/// Confidence level of the VaR / Expected Shortfall figures in `Metrics`.
+pub const RISK_CONFIDENCE: f64 = 0.95;
 
 pub struct Metrics {
     pub log_returns: Vec<f64>,
     pub sharpe_ratio: f64,
     pub max_drawdown: f64,
     pub total_pnl: f64,
+    /// Historical VaR of the per-candle log returns, as a positive loss.
+    pub value_at_risk: f64,
+    /// Historical Expected Shortfall (CVaR) of the per-candle log returns.
+    pub expected_shortfall: f64,
+    /// VaR assuming normally distributed log returns.
+    pub parametric_var: f64,
+    pub downside_deviation: f64,
+    pub skewness: f64,
+    pub excess_kurtosis: f64,
+    /// Probability that the true Sharpe ratio is above zero, given the
+    /// sample length, skewness and kurtosis of the returns.
+    pub probabilistic_sharpe: f64,
 }
 
 pub fn compute_metrics(equity_curve: &[EquitySnapshot]) -> Metrics {
     let equity: Vec<f64> = equity_curve.iter().map(|snap| snap.equity).collect();
-    let log_returns = return_quant::log_return(&equity.iter().map(|v| *v as u64).collect::<Vec<u64>>());
+    let log_returns: Vec<f64> = return_quant::log_return_f64(&equity).into_iter().flatten().collect();
     let sharpe_ratio = sharp_ratio::sharpe_ratio(&log_returns, 0.0);
     let max_drawdown = compute_max_drawdown(&equity);
     let total_pnl = equity.last().unwrap_or(&0.0) - equity.first().unwrap_or(&0.0);
+    let skewness = risk::skewness(&log_returns);
+    let excess_kurtosis = risk::excess_kurtosis(&log_returns);
     Metrics {
+        probabilistic_sharpe: probabilistic_sharpe_ratio(sharpe_ratio, 0.0, log_returns.len(), skewness, excess_kurtosis),
+        value_at_risk: risk::historical_var(&log_returns, RISK_CONFIDENCE),
+        expected_shortfall: risk::historical_es(&log_returns, RISK_CONFIDENCE),
+        parametric_var: risk::parametric_var(&log_returns, RISK_CONFIDENCE),
+        downside_deviation: risk::downside_deviation(&log_returns, 0.0),
+        skewness,
+        excess_kurtosis,
         log_returns,
         sharpe_ratio,
         max_drawdown,
@@ -23,9 +45,59 @@ pub fn compute_metrics(equity_curve: &[EquitySnapshot]) -> Metrics {
     }
 }
 
-fn compute_max_drawdown(equity: &[f64]) -> f64 {
+/// Probabilistic Sharpe Ratio (Bailey & Lopez de Prado): the probability
+/// that the true Sharpe ratio exceeds `benchmark`, given a `sharpe` estimated
+/// from `observations` returns with the given skewness and excess kurtosis.
+/// Sharpe ratios are per period, not annualized.
+pub fn probabilistic_sharpe_ratio(
+    sharpe: f64,
+    benchmark: f64,
+    observations: usize,
+    skewness: f64,
+    excess_kurtosis: f64,
+) -> f64 {
+    if observations < 2 {
+        return 0.0;
+    }
+    // (kurtosis - 1) / 4 with kurtosis = excess + 3
+    let variance = 1.0 - skewness * sharpe + (excess_kurtosis + 2.0) / 4.0 * sharpe * sharpe;
+    if variance <= 0.0 {
+        return if sharpe > benchmark { 1.0 } else { 0.0 };
+    }
+    risk::normal_cdf((sharpe - benchmark) * ((observations - 1) as f64).sqrt() / variance.sqrt())
+}
+
+/// Expected maximum Sharpe ratio of `trials` independent strategies with no
+/// skill, whose Sharpe estimates have variance `sharpe_variance`.
+pub fn expected_max_sharpe(trials: usize, sharpe_variance: f64) -> f64 {
+    if trials < 2 || sharpe_variance <= 0.0 {
+        return 0.0;
+    }
+    const EULER_MASCHERONI: f64 = 0.5772156649015329;
+    let n = trials as f64;
+    sharpe_variance.sqrt()
+        * ((1.0 - EULER_MASCHERONI) * risk::inverse_normal_cdf(1.0 - 1.0 / n)
+            + EULER_MASCHERONI * risk::inverse_normal_cdf(1.0 - 1.0 / (n * std::f64::consts::E)))
+}
+
+/// Deflated Sharpe Ratio: the PSR against the Sharpe ratio the best of
+/// `trials` skill-less configurations would be expected to reach by luck.
+pub fn deflated_sharpe_ratio(
+    sharpe: f64,
+    observations: usize,
+    skewness: f64,
+    excess_kurtosis: f64,
+    trials: usize,
+    sharpe_variance: f64,
+) -> f64 {
+    let benchmark = expected_max_sharpe(trials, sharpe_variance);
+    probabilistic_sharpe_ratio(sharpe, benchmark, observations, skewness, excess_kurtosis)
+}
+
+/// Largest peak-to-trough drop of the curve, as a fraction of the peak.
+pub fn compute_max_drawdown(equity: &[f64]) -> f64 {
     let mut max_drawdown = 0.0;
-    let mut peak = std::f64::MIN;
+    let mut peak = f64::MIN;
     for &value in equity {
         if value > peak {
             peak = value;
@@ -36,4 +108,113 @@ fn compute_max_drawdown(equity: &[f64]) -> f64 {
         }
     }
     max_drawdown
-}
\ No newline at end of file
+}
+
+/// Drawdown from the running peak for every point of the equity curve.
+pub fn drawdown_series(equity: &[f64]) -> Vec<f64> {
+    let mut peak = f64::MIN;
+    equity
+        .iter()
+        .map(|&value| {
+            if value > peak {
+                peak = value;
+            }
+            if peak > 0.0 { (peak - value) / peak } else { 0.0 }
+        })
+        .collect()
+}
+
+/// Metrics over a trailing window of `window` candles, one entry per equity
+/// snapshot so they line up with `timestamps`. Entries are `None` until the
+/// window is full.
+pub struct RollingMetrics {
+    pub window: usize,
+    pub timestamps: Vec<i64>,
+    /// Equity change over the window, e.g. `0.02` for +2%.
+    pub returns: Vec<Option<f64>>,
+    /// Standard deviation of the per-candle log returns in the window.
+    pub volatility: Vec<Option<f64>>,
+    pub sharpe_ratio: Vec<Option<f64>>,
+    /// Largest peak-to-trough drop inside the window.
+    pub max_drawdown: Vec<Option<f64>>,
+}
+
+pub fn compute_rolling_metrics(equity_curve: &[EquitySnapshot], window: usize) -> RollingMetrics {
+    let window = window.max(1);
+    let equity: Vec<f64> = equity_curve.iter().map(|snap| snap.equity).collect();
+    let log_returns: Vec<f64> = return_quant::log_return_f64(&equity).into_iter().map(|r| r.unwrap_or(0.0)).collect();
+
+    let len = equity.len();
+    let mut rolling = RollingMetrics {
+        window,
+        timestamps: equity_curve.iter().map(|snap| snap.timestamp).collect(),
+        returns: vec![None; len],
+        volatility: vec![None; len],
+        sharpe_ratio: vec![None; len],
+        max_drawdown: vec![None; len],
+    };
+
+    for i in window..len {
+        // log_returns[0] is a placeholder, so the window's returns start at i - window + 1
+        let returns = &log_returns[i - window + 1..=i];
+        let start = equity[i - window];
+        rolling.returns[i] = Some(if start != 0.0 { equity[i] / start - 1.0 } else { 0.0 });
+        rolling.volatility[i] = Some(sharp_ratio::std_deviation(returns));
+        rolling.sharpe_ratio[i] = Some(sharp_ratio::sharpe_ratio(returns, 0.0));
+        rolling.max_drawdown[i] = Some(compute_max_drawdown(&equity[i - window..=i]));
+    }
+
+    rolling
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curve(equity: &[f64]) -> Vec<EquitySnapshot> {
+        equity
+            .iter()
+            .enumerate()
+            .map(|(i, &equity)| EquitySnapshot { timestamp: i as i64 * 60, cash: equity, equity, position_value: 0.0, ..Default::default() })
+            .collect()
+    }
+
+    #[test]
+    fn test_rolling_metrics_alignment() {
+        let rolling = compute_rolling_metrics(&curve(&[100.0, 110.0, 99.0, 99.0, 120.0]), 2);
+        assert_eq!(rolling.timestamps, vec![0, 60, 120, 180, 240]);
+        assert_eq!(rolling.returns[..2], [None, None]);
+        assert!((rolling.returns[2].unwrap() + 0.01).abs() < 1e-12);
+        assert!((rolling.max_drawdown[2].unwrap() - 0.1).abs() < 1e-12);
+        assert_eq!(rolling.max_drawdown[4], Some(0.0));
+
+        let returns = [(99.0f64 / 99.0).ln(), (120.0f64 / 99.0).ln()];
+        assert_eq!(rolling.volatility[4], Some(sharp_ratio::std_deviation(&returns)));
+        assert_eq!(rolling.sharpe_ratio[4], Some(sharp_ratio::sharpe_ratio(&returns, 0.0)));
+    }
+
+    #[test]
+    fn test_probabilistic_and_deflated_sharpe() {
+        // Normal returns: PSR = Phi(SR * sqrt(n - 1) / sqrt(1 + SR^2 / 2))
+        let psr = probabilistic_sharpe_ratio(0.1, 0.0, 101, 0.0, 0.0);
+        let expected = risk::normal_cdf(0.1 * 10.0 / (1.0f64 + 0.005).sqrt());
+        assert!((psr - expected).abs() < 1e-12);
+        assert!((probabilistic_sharpe_ratio(0.0, 0.0, 50, 0.0, 0.0) - 0.5).abs() < 1e-7);
+        // Negative skew and fat tails make the same Sharpe less convincing
+        assert!(probabilistic_sharpe_ratio(0.1, 0.0, 101, -1.0, 5.0) < psr);
+
+        // More trials raise the bar
+        assert_eq!(expected_max_sharpe(1, 0.01), 0.0);
+        assert!(expected_max_sharpe(100, 0.01) > expected_max_sharpe(10, 0.01));
+        let dsr = deflated_sharpe_ratio(0.1, 101, 0.0, 0.0, 50, 0.002);
+        assert!(dsr < psr);
+        assert_eq!(deflated_sharpe_ratio(0.1, 101, 0.0, 0.0, 1, 0.002), psr);
+    }
+
+    #[test]
+    fn test_rolling_metrics_short_curve() {
+        let rolling = compute_rolling_metrics(&curve(&[100.0, 101.0]), 5);
+        assert_eq!(rolling.sharpe_ratio, vec![None, None]);
+        assert!(compute_rolling_metrics(&[], 5).timestamps.is_empty());
+    }
+}
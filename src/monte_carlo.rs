@@ -0,0 +1,375 @@
+// src/monte_carlo.rs
+//
+// Monte Carlo robustness checks: replay a backtest's round-trip trade returns
+// or per-candle equity returns in random orders and look at the spread of
+// outcomes instead of the single realised equity curve.
+
+use crate::backtest::BacktestResult;
+use crate::metrics::compute_max_drawdown;
+use crate::rng::Rng;
+use crate::types::{Signal, Trade};
+
+/// How trade returns are redrawn for each simulated run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resampling {
+    /// Same trades in a random order. Final equity is unchanged, only the
+    /// path (and so the drawdown) differs.
+    Shuffle,
+    /// Draw as many trades as were taken, with replacement.
+    Bootstrap,
+}
+
+/// Sorted outcomes of all simulated runs for one statistic.
+#[derive(Debug, Clone)]
+pub struct Distribution {
+    pub values: Vec<f64>,
+}
+
+impl Distribution {
+    pub fn new(mut values: Vec<f64>) -> Self {
+        values.sort_by(|a, b| a.total_cmp(b));
+        Self { values }
+    }
+
+    pub fn mean(&self) -> f64 {
+        sharp_ratio::average(&self.values)
+    }
+
+    /// Linearly interpolated percentile, `p` in `[0, 1]`.
+    pub fn percentile(&self, p: f64) -> f64 {
+        if self.values.is_empty() {
+            return 0.0;
+        }
+        let rank = p.clamp(0.0, 1.0) * (self.values.len() - 1) as f64;
+        let (lo, hi) = (rank.floor() as usize, rank.ceil() as usize);
+        let weight = rank - lo as f64;
+        self.values[lo] * (1.0 - weight) + self.values[hi] * weight
+    }
+
+    /// Central interval containing `level` (e.g. 0.9) of the outcomes.
+    pub fn confidence_interval(&self, level: f64) -> (f64, f64) {
+        let tail = (1.0 - level) / 2.0;
+        (self.percentile(tail), self.percentile(1.0 - tail))
+    }
+}
+
+pub struct MonteCarloResult {
+    pub final_equity: Distribution,
+    pub max_drawdown: Distribution,
+    pub sharpe_ratio: Distribution,
+}
+
+/// Seeded Monte Carlo runner; the same seed gives the same distributions.
+pub struct MonteCarlo {
+    pub iterations: usize,
+    pub seed: u64,
+    /// Length of the contiguous blocks drawn when bootstrapping equity
+    /// returns; keeps some of the autocorrelation of the original curve.
+    pub block_size: usize,
+}
+
+impl MonteCarlo {
+    pub fn new(iterations: usize, seed: u64) -> Self {
+        Self { iterations, seed, block_size: 1 }
+    }
+
+    pub fn with_block_size(mut self, block_size: usize) -> Self {
+        self.block_size = block_size.max(1);
+        self
+    }
+
+    /// Simulate by shuffling or resampling the round trips, each as its
+    /// return on the equity at entry (see `round_trip_equity_returns`).
+    pub fn trades(&self, result: &BacktestResult, method: Resampling) -> MonteCarloResult {
+        let returns = round_trip_equity_returns(result);
+        let mut rng = Rng::new(self.seed);
+        let mut sample = returns.clone();
+        self.simulate(starting_equity(result), |_| {
+            match method {
+                Resampling::Shuffle => rng.shuffle(&mut sample),
+                Resampling::Bootstrap => {
+                    for value in sample.iter_mut() {
+                        *value = returns[rng.below(returns.len())];
+                    }
+                }
+            }
+            sample.clone()
+        })
+    }
+
+    /// Simulate by block-bootstrapping the per-candle equity returns.
+    pub fn equity_returns(&self, result: &BacktestResult) -> MonteCarloResult {
+        let returns: Vec<f64> = result
+            .equity_curve
+            .windows(2)
+            .map(|pair| if pair[0].equity != 0.0 { pair[1].equity / pair[0].equity - 1.0 } else { 0.0 })
+            .collect();
+        let mut rng = Rng::new(self.seed);
+        let block = self.block_size.min(returns.len()).max(1);
+        self.simulate(starting_equity(result), |_| {
+            let mut sample = Vec::with_capacity(returns.len());
+            while sample.len() < returns.len() {
+                let start = rng.below(returns.len() - block + 1);
+                sample.extend_from_slice(&returns[start..start + block]);
+            }
+            sample.truncate(returns.len());
+            sample
+        })
+    }
+
+    fn simulate<F>(&self, start: f64, mut draw: F) -> MonteCarloResult
+    where
+        F: FnMut(usize) -> Vec<f64>,
+    {
+        let mut final_equity = Vec::with_capacity(self.iterations);
+        let mut max_drawdown = Vec::with_capacity(self.iterations);
+        let mut sharpe_ratio = Vec::with_capacity(self.iterations);
+
+        for run in 0..self.iterations {
+            let returns = draw(run);
+            let mut path = Vec::with_capacity(returns.len() + 1);
+            path.push(start);
+            for r in &returns {
+                let last = path[path.len() - 1];
+                path.push(last * (1.0 + r));
+            }
+            let log_returns: Vec<f64> = returns.iter().map(|r| (1.0 + r).max(f64::MIN_POSITIVE).ln()).collect();
+
+            final_equity.push(path[path.len() - 1]);
+            max_drawdown.push(compute_max_drawdown(&path));
+            sharpe_ratio.push(sharp_ratio::sharpe_ratio(&log_returns, 0.0));
+        }
+
+        MonteCarloResult {
+            final_equity: Distribution::new(final_equity),
+            max_drawdown: Distribution::new(max_drawdown),
+            sharpe_ratio: Distribution::new(sharpe_ratio),
+        }
+    }
+}
+
+/// Net return of every closed round trip (buy then sell for longs, sell then
+/// buy for shorts), including the fees of both legs, relative to the entry
+/// notional. Partial fills are summed until the position is flat again. An
+/// open position at the end is ignored.
+pub fn round_trip_returns(trades: &[Trade]) -> Vec<f64> {
+    round_trips(trades)
+        .iter()
+        .filter(|trip| trip.entry > 0.0)
+        .map(|trip| trip.net_pnl() / (trip.entry + trip.entry_fees))
+        .collect()
+}
+
+/// Net P&L of every closed round trip as a share of the equity just before
+/// it was opened, i.e. what it added to the equity curve. Unlike
+/// `round_trip_returns` this stays right when entries use only part of the
+/// equity (position sizers, pyramiding).
+pub fn round_trip_equity_returns(result: &BacktestResult) -> Vec<f64> {
+    let start = starting_equity(result);
+    let curve = &result.equity_curve;
+    round_trips(&result.trades)
+        .iter()
+        .filter_map(|trip| {
+            let before = match curve.partition_point(|snap| snap.timestamp < trip.opened) {
+                0 => start,
+                i => curve[i - 1].equity,
+            };
+            (before > 0.0).then(|| trip.net_pnl() / before)
+        })
+        .collect()
+}
+
+fn round_trips(trades: &[Trade]) -> Vec<RoundTrip> {
+    let mut closed = Vec::new();
+    let mut open: Option<RoundTrip> = None;
+    for trade in trades {
+        if trade.action == Signal::Hold {
+            continue;
+        }
+        let notional = trade.price * trade.size;
+        let trip = open.get_or_insert(RoundTrip {
+            direction: trade.action,
+            opened: trade.timestamp,
+            size: 0.0,
+            entry: 0.0,
+            exit: 0.0,
+            entry_fees: 0.0,
+            exit_fees: 0.0,
+        });
+        if trade.action == trip.direction {
+            trip.size += trade.size;
+            trip.entry += notional;
+            trip.entry_fees += trade.fee;
+            continue;
+        }
+        trip.size -= trade.size;
+        trip.exit += notional;
+        trip.exit_fees += trade.fee;
+        if trip.size <= 1e-9 * trade.size {
+            closed.extend(open.take());
+        }
+    }
+    closed
+}
+
+struct RoundTrip {
+    direction: Signal,
+    /// Timestamp of the first entry fill.
+    opened: i64,
+    size: f64,
+    entry: f64,
+    exit: f64,
+    entry_fees: f64,
+    exit_fees: f64,
+}
+
+impl RoundTrip {
+    fn net_pnl(&self) -> f64 {
+        let gross = if self.direction == Signal::Buy { self.exit - self.entry } else { self.entry - self.exit };
+        gross - self.entry_fees - self.exit_fees
+    }
+}
+
+fn starting_equity(result: &BacktestResult) -> f64 {
+    result.equity_curve.first().map(|snap| snap.equity).unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::EquitySnapshot;
+
+    fn trade(action: Signal, price: f64) -> Trade {
+        Trade { timestamp: 0, action, price, size: 1.0, fee: 0.0, liquidation: false, closing: false, remaining: 0.0, realized_pnl: 0.0, slippage: 0.0 }
+    }
+
+    fn result(trades: Vec<Trade>, equity: &[f64]) -> BacktestResult {
+        let equity_curve = equity
+            .iter()
+            .enumerate()
+            .map(|(i, &equity)| EquitySnapshot { timestamp: i as i64, cash: equity, equity, position_value: 0.0, ..Default::default() })
+            .collect();
+        BacktestResult { trades, equity_curve, diagnostics: Vec::new(), margin_calls: Vec::new(), terminated: None }
+    }
+
+    #[test]
+    fn test_round_trip_returns() {
+        let trades = vec![
+            trade(Signal::Buy, 100.0),
+            trade(Signal::Sell, 110.0),
+            trade(Signal::Buy, 100.0),
+            trade(Signal::Sell, 95.0),
+            trade(Signal::Buy, 90.0),
+        ];
+        let returns = round_trip_returns(&trades);
+        assert_eq!(returns.len(), 2);
+        assert!((returns[0] - 0.1).abs() < 1e-12);
+        assert!((returns[1] + 0.05).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_round_trip_returns_short() {
+        // A short closed lower, then a long liquidated (two sells in a row)
+        let trades = vec![
+            trade(Signal::Sell, 100.0),
+            trade(Signal::Buy, 90.0),
+            trade(Signal::Buy, 100.0),
+            trade(Signal::Sell, 80.0),
+            trade(Signal::Sell, 80.0),
+        ];
+        let returns = round_trip_returns(&trades);
+        assert_eq!(returns.len(), 2);
+        assert!((returns[0] - 0.1).abs() < 1e-12);
+        assert!((returns[1] + 0.2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_round_trip_returns_partial_fills() {
+        let fill = |action, price, size| Trade { size, ..trade(action, price) };
+        let trades = vec![fill(Signal::Buy, 100.0, 1.0), fill(Signal::Buy, 110.0, 1.0), fill(Signal::Sell, 126.0, 2.0)];
+        let returns = round_trip_returns(&trades);
+        assert_eq!(returns.len(), 1);
+        assert!((returns[0] - 0.2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_shuffle_keeps_final_equity() {
+        // Every trip puts the whole equity in
+        let fill = |timestamp, action, price, size| Trade { timestamp, size, ..trade(action, price) };
+        let trades = vec![
+            fill(1, Signal::Buy, 100.0, 10.0),
+            fill(2, Signal::Sell, 120.0, 10.0),
+            fill(3, Signal::Buy, 100.0, 12.0),
+            fill(4, Signal::Sell, 80.0, 12.0),
+            fill(5, Signal::Buy, 100.0, 9.6),
+            fill(6, Signal::Sell, 90.0, 9.6),
+        ];
+        let result = result(trades, &[1000.0, 1000.0, 1200.0, 1200.0, 960.0, 960.0, 864.0]);
+        let mc = MonteCarlo::new(200, 3).trades(&result, Resampling::Shuffle);
+        let expected = 1000.0 * 1.2 * 0.8 * 0.9;
+        assert!(mc.final_equity.values.iter().all(|v| (v - expected).abs() < 1e-9));
+        // Back-to-back losses give a deeper drawdown than losses split by the win
+        assert!(mc.max_drawdown.percentile(0.0) < mc.max_drawdown.percentile(1.0));
+    }
+
+    #[test]
+    fn test_trades_weighted_by_position_size() {
+        use crate::backtest::backtest;
+        use crate::sizing::FixedFractional;
+        use crate::strategy::Strategy;
+        use crate::trade_model::TradeModel;
+        use crate::types::Candle;
+
+        #[derive(Clone)]
+        struct Scripted(Vec<Signal>);
+
+        impl Strategy for Scripted {
+            fn next(&mut self, _candle: &Candle) -> Signal {
+                if self.0.is_empty() { Signal::Hold } else { self.0.remove(0) }
+            }
+            fn reset(&mut self) {}
+            fn box_clone(&self) -> Box<dyn Strategy> {
+                Box::new(self.clone())
+            }
+        }
+
+        let candles: Vec<Candle> = [100.0, 120.0, 100.0, 80.0]
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| Candle { timestamp: i as i64, open: close, high: close, low: close, close, volume: 1.0, period: 1.0 })
+            .collect();
+        let mut strategy = Scripted(vec![Signal::Buy, Signal::Sell, Signal::Buy, Signal::Sell]);
+        let mut model = TradeModel::new(1000.0, 0.0, 0.0, 1.0).with_sizer(Box::new(FixedFractional::new(0.25)));
+        let result = backtest(&candles, &mut strategy, &mut model);
+
+        // +20% and -20% on a quarter of the equity move it by +5% and -5%
+        let returns = round_trip_equity_returns(&result);
+        assert!((returns[0] - 0.05).abs() < 1e-12 && (returns[1] + 0.05).abs() < 1e-12);
+        let actual = result.equity_curve.last().unwrap().equity;
+        assert!((actual - 997.5).abs() < 1e-9);
+        let mc = MonteCarlo::new(50, 1).trades(&result, Resampling::Shuffle);
+        assert!(mc.final_equity.values.iter().all(|v| (v - actual).abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_bootstrap_is_reproducible() {
+        let result = result(Vec::new(), &[100.0, 102.0, 99.0, 105.0, 103.0, 108.0, 104.0]);
+        let a = MonteCarlo::new(500, 11).with_block_size(2).equity_returns(&result);
+        let b = MonteCarlo::new(500, 11).with_block_size(2).equity_returns(&result);
+        assert_eq!(a.final_equity.values, b.final_equity.values);
+        assert_eq!(a.sharpe_ratio.values, b.sharpe_ratio.values);
+
+        let (lo, hi) = a.final_equity.confidence_interval(0.9);
+        assert!(lo < 108.0 && 108.0 < hi);
+        assert!(lo <= a.final_equity.mean() && a.final_equity.mean() <= hi);
+    }
+
+    #[test]
+    fn test_distribution_percentile() {
+        let dist = Distribution::new(vec![4.0, 1.0, 3.0, 2.0, 5.0]);
+        assert_eq!(dist.percentile(0.5), 3.0);
+        assert_eq!(dist.percentile(0.125), 1.5);
+        assert_eq!(dist.confidence_interval(1.0), (1.0, 5.0));
+        assert_eq!(Distribution::new(Vec::new()).percentile(0.5), 0.0);
+    }
+}
@@ -0,0 +1,265 @@
+// src/optimize.rs
+//
+// Parameter sweeps over registered strategies, with the multiple-testing
+// checks that keep the best-looking configuration honest: the Deflated
+// Sharpe Ratio and combinatorially purged cross-validation (CPCV).
+
+use crate::backtest::backtest;
+use crate::metrics::{compute_metrics, deflated_sharpe_ratio, Metrics};
+use crate::strategy::{ParamValue, Params, Registry, RegistryError};
+use crate::trade_model::TradeModel;
+use crate::types::Candle;
+
+/// Every combination of the given values, e.g.
+/// `param_grid(&[("short_period", vec![Int(5), Int(9)]), ("long_period", vec![Int(21)])])`.
+pub fn param_grid(axes: &[(&str, Vec<ParamValue>)]) -> Vec<Params> {
+    let mut combos = vec![Params::new()];
+    for (name, values) in axes {
+        combos = combos
+            .into_iter()
+            .flat_map(|combo| {
+                values.iter().map(move |value| {
+                    let mut next = combo.clone();
+                    next.insert(name.to_string(), value.clone());
+                    next
+                })
+            })
+            .collect();
+    }
+    combos
+}
+
+pub struct SweepResult {
+    pub params: Params,
+    pub label: String,
+    pub metrics: Metrics,
+    /// Sharpe ratio deflated for the number of configurations tried.
+    pub deflated_sharpe: f64,
+}
+
+/// Backtest `name` with every parameter set in `combos` on the same candles.
+/// Combinations with inverted periods (e.g. short above long) are skipped.
+/// Results are sorted by raw Sharpe ratio, best first.
+pub fn sweep<F>(
+    registry: &Registry,
+    name: &str,
+    combos: &[Params],
+    candles: &[Candle],
+    trade_model: F,
+) -> Result<Vec<SweepResult>, RegistryError>
+where
+    F: Fn() -> TradeModel,
+{
+    let mut results = Vec::with_capacity(combos.len());
+    for params in combos {
+        let mut strategy = match registry.create(name, params) {
+            Err(RegistryError::ParamOrder { .. }) => continue,
+            created => created?,
+        };
+        let mut model = trade_model();
+        if model.sizer.is_none() {
+            model.sizer = strategy.sizer();
+        }
+        let result = backtest(candles, strategy.as_mut(), &mut model);
+        results.push(SweepResult {
+            params: params.clone(),
+            label: registry.label(name, params)?,
+            metrics: compute_metrics(&result.equity_curve),
+            deflated_sharpe: 0.0,
+        });
+    }
+
+    let trials = results.len();
+    let sharpes: Vec<f64> = results.iter().map(|r| r.metrics.sharpe_ratio).collect();
+    let sharpe_variance = sharp_ratio::std_deviation(&sharpes).powi(2);
+    for result in &mut results {
+        let m = &result.metrics;
+        result.deflated_sharpe =
+            deflated_sharpe_ratio(m.sharpe_ratio, m.log_returns.len(), m.skewness, m.excess_kurtosis, trials, sharpe_variance);
+    }
+
+    results.sort_by(|a, b| b.metrics.sharpe_ratio.total_cmp(&a.metrics.sharpe_ratio));
+    Ok(results)
+}
+
+/// Combinatorially purged cross-validation of a sweep.
+///
+/// The return series is cut into `groups` contiguous blocks. For every choice
+/// of `test_groups` blocks, the configuration with the best Sharpe ratio on
+/// the remaining (training) blocks is selected and scored on the test
+/// blocks. Training returns within `purge` candles of a test block are
+/// dropped, so indicator state leaking across the boundary cannot help.
+#[derive(Debug, Clone)]
+pub struct PurgedCv {
+    pub groups: usize,
+    pub test_groups: usize,
+    pub purge: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct CvSplit {
+    pub test_groups: Vec<usize>,
+    /// Index into the sweep results of the configuration picked in-sample.
+    pub selected: usize,
+    pub in_sample_sharpe: f64,
+    pub out_of_sample_sharpe: f64,
+    /// Share of the other configurations the selected one beat out of sample.
+    pub out_of_sample_rank: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct CvReport {
+    pub splits: Vec<CvSplit>,
+    pub mean_in_sample_sharpe: f64,
+    pub mean_out_of_sample_sharpe: f64,
+    /// Probability of Backtest Overfitting: share of splits where the
+    /// in-sample winner ranks in the bottom half out of sample.
+    pub probability_of_overfitting: f64,
+}
+
+impl PurgedCv {
+    pub fn new(groups: usize, test_groups: usize, purge: usize) -> Self {
+        let groups = groups.max(2);
+        Self { groups, test_groups: test_groups.clamp(1, groups - 1), purge }
+    }
+
+    pub fn run(&self, results: &[SweepResult]) -> CvReport {
+        let len = results.iter().map(|r| r.metrics.log_returns.len()).min().unwrap_or(0);
+        let mut splits = Vec::new();
+
+        if !results.is_empty() && len >= self.groups {
+            let bounds: Vec<(usize, usize)> = (0..self.groups)
+                .map(|g| (g * len / self.groups, (g + 1) * len / self.groups))
+                .collect();
+
+            for test_groups in combinations(self.groups, self.test_groups) {
+                let is_test = |i: usize| test_groups.iter().any(|&g| bounds[g].0 <= i && i < bounds[g].1);
+                let near_test = |i: usize| {
+                    test_groups.iter().any(|&g| {
+                        let (start, end) = bounds[g];
+                        i + self.purge >= start && i < end + self.purge
+                    })
+                };
+                let test: Vec<usize> = (0..len).filter(|&i| is_test(i)).collect();
+                let train: Vec<usize> = (0..len).filter(|&i| !near_test(i)).collect();
+                if train.len() < 2 || test.len() < 2 {
+                    continue;
+                }
+
+                let score = |result: &SweepResult, indices: &[usize]| {
+                    let returns: Vec<f64> = indices.iter().map(|&i| result.metrics.log_returns[i]).collect();
+                    sharp_ratio::sharpe_ratio(&returns, 0.0)
+                };
+                let in_sample: Vec<f64> = results.iter().map(|r| score(r, &train)).collect();
+                let out_of_sample: Vec<f64> = results.iter().map(|r| score(r, &test)).collect();
+
+                let selected = (0..results.len())
+                    .max_by(|&a, &b| in_sample[a].total_cmp(&in_sample[b]))
+                    .unwrap_or(0);
+                let beaten = out_of_sample.iter().filter(|&&s| s < out_of_sample[selected]).count();
+                let out_of_sample_rank = if results.len() > 1 { beaten as f64 / (results.len() - 1) as f64 } else { 1.0 };
+
+                splits.push(CvSplit {
+                    test_groups,
+                    selected,
+                    in_sample_sharpe: in_sample[selected],
+                    out_of_sample_sharpe: out_of_sample[selected],
+                    out_of_sample_rank,
+                });
+            }
+        }
+
+        let count = splits.len().max(1) as f64;
+        CvReport {
+            mean_in_sample_sharpe: splits.iter().map(|s| s.in_sample_sharpe).sum::<f64>() / count,
+            mean_out_of_sample_sharpe: splits.iter().map(|s| s.out_of_sample_sharpe).sum::<f64>() / count,
+            probability_of_overfitting: splits.iter().filter(|s| s.out_of_sample_rank < 0.5).count() as f64 / count,
+            splits,
+        }
+    }
+}
+
+/// All `k`-element subsets of `0..n`, in lexicographic order.
+fn combinations(n: usize, k: usize) -> Vec<Vec<usize>> {
+    let mut result = Vec::new();
+    let mut current: Vec<usize> = (0..k).collect();
+    if k == 0 || k > n {
+        return result;
+    }
+    loop {
+        result.push(current.clone());
+        // Advance the rightmost element that still has room
+        let Some(i) = (0..k).rev().find(|&i| current[i] < n - k + i) else {
+            return result;
+        };
+        current[i] += 1;
+        for j in i + 1..k {
+            current[j] = current[j - 1] + 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::synthetic::{PriceModel, SyntheticMarket};
+
+    #[test]
+    fn test_param_grid_and_combinations() {
+        let grid = param_grid(&[
+            ("short_period", vec![ParamValue::Int(5), ParamValue::Int(9)]),
+            ("long_period", vec![ParamValue::Int(21), ParamValue::Int(30), ParamValue::Int(50)]),
+        ]);
+        assert_eq!(grid.len(), 6);
+        assert_eq!(grid[5].get("short_period"), Some(&ParamValue::Int(9)));
+        assert_eq!(grid[5].get("long_period"), Some(&ParamValue::Int(50)));
+
+        assert_eq!(combinations(4, 2), vec![vec![0, 1], vec![0, 2], vec![0, 3], vec![1, 2], vec![1, 3], vec![2, 3]]);
+        assert_eq!(combinations(6, 2).len(), 15);
+        assert!(combinations(2, 3).is_empty());
+    }
+
+    #[test]
+    fn test_sweep_and_purged_cv_on_noise() {
+        // Pure noise: no configuration has real skill, so the deflated Sharpe
+        // should be well below the raw PSR of the winner
+        let candles = SyntheticMarket::new(PriceModel::Gbm { drift: 0.0, volatility: 0.01 }, 21).generate(1_500);
+        let grid = param_grid(&[
+            ("short_period", vec![ParamValue::Int(3), ParamValue::Int(5), ParamValue::Int(9)]),
+            ("long_period", vec![ParamValue::Int(15), ParamValue::Int(30), ParamValue::Int(60)]),
+        ]);
+        let registry = Registry::builtin();
+        let results = sweep(&registry, "ema_cross", &grid, &candles, || TradeModel::new(1000.0, 0.0, 0.0, 1.0)).unwrap();
+
+        assert_eq!(results.len(), 9);
+        assert!(results.windows(2).all(|w| w[0].metrics.sharpe_ratio >= w[1].metrics.sharpe_ratio));
+        assert!(results[0].label.starts_with("EMA Cross"));
+        assert!(results[0].deflated_sharpe < results[0].metrics.probabilistic_sharpe);
+
+        let report = PurgedCv::new(6, 2, 10).run(&results);
+        assert_eq!(report.splits.len(), 15);
+        assert!((0.0..=1.0).contains(&report.probability_of_overfitting));
+        // Selection is in-sample, so it is optimistic compared to the test blocks
+        assert!(report.mean_in_sample_sharpe >= report.mean_out_of_sample_sharpe);
+        assert!(report.splits.iter().all(|s| s.selected < results.len()));
+    }
+
+    #[test]
+    fn test_sweep_skips_inverted_periods() {
+        let candles = SyntheticMarket::new(PriceModel::Gbm { drift: 0.0, volatility: 0.01 }, 3).generate(200);
+        let grid = param_grid(&[
+            ("short_period", vec![ParamValue::Int(5), ParamValue::Int(30)]),
+            ("long_period", vec![ParamValue::Int(20), ParamValue::Int(40)]),
+        ]);
+        let results = sweep(&Registry::builtin(), "sma_cross", &grid, &candles, || TradeModel::new(1000.0, 0.0, 0.0, 1.0)).unwrap();
+        // 30/20 is dropped
+        assert_eq!(results.len(), 3);
+        assert!(results.iter().all(|r| r.params["short_period"] != ParamValue::Int(30) || r.params["long_period"] == ParamValue::Int(40)));
+    }
+
+    #[test]
+    fn test_unknown_strategy() {
+        let result = sweep(&Registry::builtin(), "nope", &[Params::new()], &[], || TradeModel::new(1.0, 0.0, 0.0, 0.0));
+        assert!(matches!(result, Err(RegistryError::UnknownStrategy(_))));
+    }
+}
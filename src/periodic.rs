@@ -0,0 +1,261 @@
+// src/periodic.rs
+//
+// Calendar-period (UTC) returns of an equity curve, so backtests on candles of
+// any size can be compared per day, week, month or year.
+
+use crate::terminal::{Align, Table};
+use crate::types::EquitySnapshot;
+
+pub const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Period {
+    Day,
+    /// ISO week, Monday to Sunday.
+    Week,
+    Month,
+    Year,
+}
+
+impl Period {
+    pub const ALL: [Period; 4] = [Period::Day, Period::Week, Period::Month, Period::Year];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Period::Day => "day",
+            Period::Week => "week",
+            Period::Month => "month",
+            Period::Year => "year",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Period::ALL.into_iter().find(|p| p.name() == name)
+    }
+
+    /// Unix timestamp of the start of the period containing `timestamp`.
+    pub fn start(&self, timestamp: i64) -> i64 {
+        let days = timestamp.div_euclid(SECONDS_PER_DAY);
+        let start_day = match self {
+            Period::Day => days,
+            // 1970-01-01 was a Thursday, three days after a Monday
+            Period::Week => days - (days + 3).rem_euclid(7),
+            Period::Month => {
+                let (year, month, _) = civil_from_unix(timestamp);
+                days_from_civil(year, month, 1)
+            }
+            Period::Year => days_from_civil(civil_from_unix(timestamp).0, 1, 1),
+        };
+        start_day * SECONDS_PER_DAY
+    }
+
+    /// Label of the period starting at `start`: `2024-10-07`, `2024-W41`,
+    /// `2024-10` or `2024`.
+    pub fn label(&self, start: i64) -> String {
+        let (year, month, day) = civil_from_unix(start);
+        match self {
+            Period::Day => format!("{:04}-{:02}-{:02}", year, month, day),
+            Period::Week => {
+                // The ISO week belongs to the year of its Thursday
+                let thursday = start.div_euclid(SECONDS_PER_DAY) + 3;
+                let iso_year = civil_from_unix(thursday * SECONDS_PER_DAY).0;
+                let week = (thursday - days_from_civil(iso_year, 1, 1)) / 7 + 1;
+                format!("{:04}-W{:02}", iso_year, week)
+            }
+            Period::Month => format!("{:04}-{:02}", year, month),
+            Period::Year => format!("{:04}", year),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeriodReturn {
+    /// Unix timestamp of the start of the period.
+    pub start: i64,
+    pub label: String,
+    /// Equity change over the period as a fraction, measured from the last
+    /// equity of the previous period (or the first snapshot).
+    pub value: f64,
+}
+
+/// Returns per calendar period, in time order. Periods without snapshots are
+/// skipped; the first and last periods may be partial.
+pub fn period_returns(equity_curve: &[EquitySnapshot], period: Period) -> Vec<PeriodReturn> {
+    let mut result = Vec::new();
+    let mut base = match equity_curve.first() {
+        Some(snap) => snap.equity,
+        None => return result,
+    };
+    let mut current: Option<(i64, f64)> = None;
+
+    for snap in equity_curve {
+        let start = period.start(snap.timestamp);
+        if let Some((current_start, last)) = current {
+            if current_start != start {
+                result.push(PeriodReturn { start: current_start, label: period.label(current_start), value: period_return(base, last) });
+                base = last;
+            }
+        }
+        current = Some((start, snap.equity));
+    }
+    if let Some((start, last)) = current {
+        result.push(PeriodReturn { start, label: period.label(start), value: period_return(base, last) });
+    }
+    result
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeriodStats {
+    pub count: usize,
+    pub average: f64,
+    pub best: Option<PeriodReturn>,
+    pub worst: Option<PeriodReturn>,
+    /// Share of periods with a return above zero, 0 to 1.
+    pub positive_share: f64,
+}
+
+pub fn period_stats(returns: &[PeriodReturn]) -> PeriodStats {
+    let values: Vec<f64> = returns.iter().map(|r| r.value).collect();
+    let positive = values.iter().filter(|&&v| v > 0.0).count();
+    PeriodStats {
+        count: returns.len(),
+        average: sharp_ratio::average(&values),
+        best: returns.iter().max_by(|a, b| a.value.total_cmp(&b.value)).cloned(),
+        worst: returns.iter().min_by(|a, b| a.value.total_cmp(&b.value)).cloned(),
+        positive_share: if returns.is_empty() { 0.0 } else { positive as f64 / returns.len() as f64 },
+    }
+}
+
+/// Terminal table of monthly returns in percent: one row per year, one
+/// column per month plus the compounded year total.
+pub fn monthly_table(equity_curve: &[EquitySnapshot]) -> String {
+    let months = period_returns(equity_curve, Period::Month);
+    let mut columns = vec![("Year", Align::Left)];
+    columns.extend(MONTHS.iter().map(|&m| (m, Align::Right)));
+    columns.push(("Year %", Align::Right));
+    let mut table = Table::new(&columns);
+
+    let years = period_returns(equity_curve, Period::Year);
+    for year in &years {
+        let mut row = vec![year.label.clone()];
+        for month in 1..=12 {
+            let cell = months
+                .iter()
+                .find(|r| {
+                    let (y, m, _) = civil_from_unix(r.start);
+                    y == civil_from_unix(year.start).0 && m == month
+                })
+                .map(|r| format!("{:.2}", r.value * 100.0))
+                .unwrap_or_default();
+            row.push(cell);
+        }
+        row.push(format!("{:.2}", year.value * 100.0));
+        table.add_row(row);
+    }
+    table.render()
+}
+
+pub fn period_return(start: f64, end: f64) -> f64 {
+    if start != 0.0 { end / start - 1.0 } else { 0.0 }
+}
+
+/// `(year, month, day)` of a Unix timestamp, in UTC.
+pub fn civil_from_unix(timestamp: i64) -> (i64, u32, u32) {
+    // Howard Hinnant's days-to-civil algorithm
+    let z = timestamp.div_euclid(SECONDS_PER_DAY) + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Days since 1970-01-01 of a UTC calendar date (inverse of `civil_from_unix`).
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(timestamp: i64, equity: f64) -> EquitySnapshot {
+        EquitySnapshot { timestamp, cash: equity, equity, position_value: 0.0, ..Default::default() }
+    }
+
+    #[test]
+    fn test_civil_from_unix() {
+        assert_eq!(civil_from_unix(0), (1970, 1, 1));
+        assert_eq!(civil_from_unix(1_709_251_199), (2024, 2, 29));
+        assert_eq!(civil_from_unix(1_735_689_600), (2025, 1, 1));
+        for days in [-1000, 0, 59, 19_782, 20_089] {
+            let (y, m, d) = civil_from_unix(days * SECONDS_PER_DAY);
+            assert_eq!(days_from_civil(y, m, d), days);
+        }
+    }
+
+    #[test]
+    fn test_period_starts_and_labels() {
+        let ts = 1_728_561_600; // Thursday 2024-10-10 12:00 UTC
+        assert_eq!(Period::Day.label(Period::Day.start(ts)), "2024-10-10");
+        assert_eq!(Period::Week.label(Period::Week.start(ts)), "2024-W41");
+        assert_eq!(Period::Week.start(ts), 1_728_259_200); // Monday 2024-10-07
+        assert_eq!(Period::Month.label(Period::Month.start(ts)), "2024-10");
+        assert_eq!(Period::Year.start(ts), 1_704_067_200);
+        // 2024-12-30 is in week 1 of 2025
+        assert_eq!(Period::Week.label(Period::Week.start(1_735_560_000)), "2025-W01");
+        assert_eq!(Period::from_name("week"), Some(Period::Week));
+    }
+
+    #[test]
+    fn test_monthly_returns() {
+        let curve = vec![
+            snapshot(1_704_067_200, 100.0), // 2024-01-01
+            snapshot(1_706_659_200, 110.0), // 2024-01-31
+            snapshot(1_706_745_600, 99.0),  // 2024-02-01
+            snapshot(1_709_164_800, 121.0), // 2024-02-29
+        ];
+        let returns = period_returns(&curve, Period::Month);
+        assert_eq!(returns.len(), 2);
+        assert_eq!(returns[0].label, "2024-01");
+        assert!((returns[0].value - 0.1).abs() < 1e-12);
+        assert!((returns[1].value - 0.1).abs() < 1e-12);
+
+        let year = period_returns(&curve, Period::Year);
+        assert_eq!(year.len(), 1);
+        assert!((year[0].value - 0.21).abs() < 1e-12);
+
+        let table = monthly_table(&curve);
+        assert!(table.contains("2024") && table.contains("10.00") && table.contains("21.00"));
+    }
+
+    #[test]
+    fn test_period_stats() {
+        let curve: Vec<EquitySnapshot> = [100.0, 102.0, 101.0, 99.0, 103.0]
+            .iter()
+            .enumerate()
+            .map(|(i, &e)| snapshot(1_704_067_200 + i as i64 * SECONDS_PER_DAY, e))
+            .collect();
+        let stats = period_stats(&period_returns(&curve, Period::Day));
+        // First day has no change: 0, +2%, ~-1%, ~-2%, ~+4%
+        assert_eq!(stats.count, 5);
+        assert!((stats.positive_share - 0.4).abs() < 1e-12);
+        assert_eq!(stats.best.unwrap().label, "2024-01-05");
+        assert_eq!(stats.worst.unwrap().label, "2024-01-04");
+        assert_eq!(period_stats(&[]).best, None);
+    }
+}
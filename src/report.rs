@@ -0,0 +1,382 @@
+// src/report.rs
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::backtest::BacktestResult;
+use crate::metrics::{drawdown_series, Metrics};
+use crate::periodic::{civil_from_unix, period_returns, period_stats, Period, PeriodReturn, MONTHS};
+use crate::types::{Candle, EquitySnapshot, Signal};
+
+const WIDTH: f64 = 900.0;
+const HEIGHT: f64 = 260.0;
+const MARGIN_LEFT: f64 = 70.0;
+const MARGIN_RIGHT: f64 = 20.0;
+const MARGIN_TOP: f64 = 15.0;
+const MARGIN_BOTTOM: f64 = 30.0;
+const GRID_LINES: usize = 4;
+
+struct Marker {
+    timestamp: i64,
+    price: f64,
+    is_buy: bool,
+}
+
+/// Render a single self-contained HTML page (inline CSS + SVG, no scripts or
+/// external assets) describing one backtest run.
+///
+/// `candles` should be the same slice the backtest was run on; it is used for
+/// the price chart that the trade markers are drawn over.
+pub fn render_html_report(
+    title: &str,
+    candles: &[Candle],
+    result: &BacktestResult,
+    metrics: &Metrics,
+) -> String {
+    let equity: Vec<(i64, f64)> = result
+        .equity_curve
+        .iter()
+        .map(|snap| (snap.timestamp, snap.equity))
+        .collect();
+    let values: Vec<f64> = equity.iter().map(|&(_, v)| v).collect();
+    let drawdown: Vec<(i64, f64)> = equity
+        .iter()
+        .zip(drawdown_series(&values))
+        .map(|(&(ts, _), dd)| (ts, -dd * 100.0))
+        .collect();
+    let prices: Vec<(i64, f64)> = candles.iter().map(|c| (c.timestamp, c.close)).collect();
+    let markers: Vec<Marker> = result
+        .trades
+        .iter()
+        .filter_map(|trade| {
+            let is_buy = match trade.action {
+                Signal::Buy => true,
+                Signal::Sell => false,
+                Signal::Hold => return None,
+            };
+            Some(Marker { timestamp: trade.timestamp, price: trade.price, is_buy })
+        })
+        .collect();
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!("<title>{}</title>\n", escape(title)));
+    html.push_str(STYLE);
+    html.push_str("</head>\n<body>\n");
+    html.push_str(&format!("<h1>{}</h1>\n", escape(title)));
+
+    html.push_str("<h2>Metrics</h2>\n");
+    html.push_str(&metrics_table(result, metrics));
+
+    html.push_str("<h2>Equity curve</h2>\n");
+    html.push_str(&line_chart(&equity, "#1f77b4", false, &[]));
+
+    html.push_str("<h2>Drawdown (%)</h2>\n");
+    html.push_str(&line_chart(&drawdown, "#d62728", true, &[]));
+
+    html.push_str("<h2>Price and trades</h2>\n");
+    html.push_str(&line_chart(&prices, "#555555", false, &markers));
+
+    html.push_str("<h2>Monthly returns (%)</h2>\n");
+    html.push_str(&monthly_heatmap(&result.equity_curve));
+
+    html.push_str("<h2>Calendar periods (UTC)</h2>\n");
+    html.push_str(&period_table(&result.equity_curve));
+
+    let diagnostic_names = result.diagnostic_names();
+    if !diagnostic_names.is_empty() {
+        html.push_str("<h2>Strategy internals</h2>\n");
+        for name in diagnostic_names {
+            html.push_str(&format!("<h3>{}</h3>\n", escape(&name)));
+            html.push_str(&line_chart(&result.diagnostic_series(&name), "#9467bd", false, &[]));
+        }
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Render the report and write it to `path`.
+pub fn write_html_report<P: AsRef<Path>>(
+    path: P,
+    title: &str,
+    candles: &[Candle],
+    result: &BacktestResult,
+    metrics: &Metrics,
+) -> io::Result<()> {
+    fs::write(path, render_html_report(title, candles, result, metrics))
+}
+
+const STYLE: &str = "<style>
+body { font-family: -apple-system, 'Segoe UI', Helvetica, Arial, sans-serif; margin: 2em auto; max-width: 960px; color: #222; }
+h1 { font-size: 1.5em; }
+h2 { font-size: 1.1em; margin-top: 1.8em; border-bottom: 1px solid #ddd; }
+h3 { font-size: 0.95em; margin: 1em 0 0.2em; }
+table { border-collapse: collapse; }
+td, th { padding: 4px 10px; border: 1px solid #ddd; text-align: right; font-variant-numeric: tabular-nums; }
+th { background: #f5f5f5; }
+td.label { text-align: left; }
+svg text { font-size: 11px; fill: #555; }
+.empty { color: #999; }
+</style>
+";
+
+fn metrics_table(result: &BacktestResult, metrics: &Metrics) -> String {
+    let start = result.equity_curve.first().map(|e| e.equity).unwrap_or(0.0);
+    let end = result.equity_curve.last().map(|e| e.equity).unwrap_or(0.0);
+    let rows = [
+        ("Starting equity", format!("{:.2}", start)),
+        ("Final equity", format!("{:.2}", end)),
+        ("Total P&amp;L", format!("{:.2}", metrics.total_pnl)),
+        ("Sharpe ratio", format!("{:.4}", metrics.sharpe_ratio)),
+        ("Max drawdown", format!("{:.2}%", metrics.max_drawdown * 100.0)),
+        ("VaR 95% (historical)", format!("{:.3}%", metrics.value_at_risk * 100.0)),
+        ("VaR 95% (parametric)", format!("{:.3}%", metrics.parametric_var * 100.0)),
+        ("Expected shortfall 95%", format!("{:.3}%", metrics.expected_shortfall * 100.0)),
+        ("Downside deviation", format!("{:.3}%", metrics.downside_deviation * 100.0)),
+        ("Skewness", format!("{:.3}", metrics.skewness)),
+        ("Excess kurtosis", format!("{:.3}", metrics.excess_kurtosis)),
+        ("Total trades", result.trades.len().to_string()),
+        ("Candles", result.equity_curve.len().to_string()),
+    ];
+
+    let mut table = String::from("<table>\n");
+    for (label, value) in rows {
+        table.push_str(&format!("<tr><td class=\"label\">{}</td><td>{}</td></tr>\n", label, value));
+    }
+    table.push_str("</table>\n");
+    table
+}
+
+/// Best / worst period and share of positive periods for each calendar period.
+fn period_table(equity_curve: &[EquitySnapshot]) -> String {
+    let mut table = String::from(
+        "<table>\n<tr><th>Period</th><th>Count</th><th>Positive</th><th>Average</th><th>Best</th><th>Worst</th></tr>\n",
+    );
+    for period in Period::ALL {
+        let stats = period_stats(&period_returns(equity_curve, period));
+        let extreme = |r: &Option<PeriodReturn>| match r {
+            Some(r) => format!("{:.2}% ({})", r.value * 100.0, r.label),
+            None => String::new(),
+        };
+        table.push_str(&format!(
+            "<tr><td class=\"label\">{}</td><td>{}</td><td>{:.0}%</td><td>{:.2}%</td><td>{}</td><td>{}</td></tr>\n",
+            period.name(),
+            stats.count,
+            stats.positive_share * 100.0,
+            stats.average * 100.0,
+            extreme(&stats.best),
+            extreme(&stats.worst),
+        ));
+    }
+    table.push_str("</table>\n");
+    table
+}
+
+fn line_chart(points: &[(i64, f64)], color: &str, fill: bool, markers: &[Marker]) -> String {
+    if points.is_empty() {
+        return "<p class=\"empty\">No data</p>\n".to_string();
+    }
+
+    let x_min = points.iter().map(|p| p.0).min().unwrap_or(0);
+    let x_max = points.iter().map(|p| p.0).max().unwrap_or(0);
+    let x_span = (x_max - x_min).max(1) as f64;
+
+    let mut y_min = f64::MAX;
+    let mut y_max = f64::MIN;
+    for y in points.iter().map(|p| p.1).chain(markers.iter().map(|m| m.price)) {
+        y_min = y_min.min(y);
+        y_max = y_max.max(y);
+    }
+    if fill {
+        y_max = y_max.max(0.0);
+    }
+    if (y_max - y_min).abs() < 1e-12 {
+        y_min -= 1.0;
+        y_max += 1.0;
+    }
+
+    let plot_w = WIDTH - MARGIN_LEFT - MARGIN_RIGHT;
+    let plot_h = HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+    let sx = |x: i64| MARGIN_LEFT + (x - x_min) as f64 / x_span * plot_w;
+    let sy = |y: f64| MARGIN_TOP + (y_max - y) / (y_max - y_min) * plot_h;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n",
+        w = WIDTH,
+        h = HEIGHT
+    );
+
+    for i in 0..=GRID_LINES {
+        let value = y_min + (y_max - y_min) * i as f64 / GRID_LINES as f64;
+        let y = sy(value);
+        svg.push_str(&format!(
+            "<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"#eee\"/>\n",
+            MARGIN_LEFT,
+            y,
+            WIDTH - MARGIN_RIGHT,
+            y
+        ));
+        svg.push_str(&format!(
+            "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"end\">{}</text>\n",
+            MARGIN_LEFT - 6.0,
+            y + 4.0,
+            format_axis_value(value)
+        ));
+    }
+
+    svg.push_str(&format!(
+        "<text x=\"{:.1}\" y=\"{:.1}\">{}</text>\n",
+        MARGIN_LEFT,
+        HEIGHT - 8.0,
+        format_date(x_min)
+    ));
+    svg.push_str(&format!(
+        "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"end\">{}</text>\n",
+        WIDTH - MARGIN_RIGHT,
+        HEIGHT - 8.0,
+        format_date(x_max)
+    ));
+
+    let mut path = String::new();
+    for (i, &(x, y)) in points.iter().enumerate() {
+        let cmd = if i == 0 { 'M' } else { 'L' };
+        path.push_str(&format!("{}{:.1},{:.1} ", cmd, sx(x), sy(y)));
+    }
+
+    if fill {
+        let baseline = sy(0.0_f64.clamp(y_min, y_max));
+        svg.push_str(&format!(
+            "<path d=\"{}L{:.1},{:.1} L{:.1},{:.1} Z\" fill=\"{}\" fill-opacity=\"0.25\" stroke=\"none\"/>\n",
+            path,
+            sx(x_max),
+            baseline,
+            sx(x_min),
+            baseline,
+            color
+        ));
+    }
+    svg.push_str(&format!(
+        "<path d=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"1.5\"/>\n",
+        path.trim_end(),
+        color
+    ));
+
+    for marker in markers {
+        let x = sx(marker.timestamp);
+        let y = sy(marker.price);
+        let (shape, color) = if marker.is_buy {
+            (format!("{:.1},{:.1} {:.1},{:.1} {:.1},{:.1}", x, y - 6.0, x - 5.0, y + 4.0, x + 5.0, y + 4.0), "#2ca02c")
+        } else {
+            (format!("{:.1},{:.1} {:.1},{:.1} {:.1},{:.1}", x, y + 6.0, x - 5.0, y - 4.0, x + 5.0, y - 4.0), "#d62728")
+        };
+        svg.push_str(&format!("<polygon points=\"{}\" fill=\"{}\"/>\n", shape, color));
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn monthly_heatmap(equity_curve: &[EquitySnapshot]) -> String {
+    let returns: Vec<(i64, u32, f64)> = period_returns(equity_curve, Period::Month)
+        .iter()
+        .map(|r| {
+            let (year, month, _) = civil_from_unix(r.start);
+            (year, month, r.value)
+        })
+        .collect();
+    if returns.is_empty() {
+        return "<p class=\"empty\">No data</p>\n".to_string();
+    }
+
+    let max_abs = returns
+        .iter()
+        .map(|&(_, _, r)| r.abs())
+        .fold(0.0, f64::max)
+        .max(1e-12);
+
+    let mut table = String::from("<table>\n<tr><th>Year</th>");
+    for month in MONTHS {
+        table.push_str(&format!("<th>{}</th>", month));
+    }
+    table.push_str("</tr>\n");
+
+    let first_year = returns.first().map(|r| r.0).unwrap_or(0);
+    let last_year = returns.last().map(|r| r.0).unwrap_or(0);
+    for year in first_year..=last_year {
+        table.push_str(&format!("<tr><th>{}</th>", year));
+        for month in 1..=12 {
+            match returns.iter().find(|&&(y, m, _)| y == year && m == month) {
+                Some(&(_, _, r)) => {
+                    let alpha = (r.abs() / max_abs * 0.8 + 0.1).min(0.9);
+                    let rgb = if r >= 0.0 { "44,160,44" } else { "214,39,40" };
+                    table.push_str(&format!(
+                        "<td style=\"background: rgba({}, {:.2})\">{:.2}</td>",
+                        rgb,
+                        alpha,
+                        r * 100.0
+                    ));
+                }
+                None => table.push_str("<td></td>"),
+            }
+        }
+        table.push_str("</tr>\n");
+    }
+    table.push_str("</table>\n");
+    table
+}
+
+fn format_date(timestamp: i64) -> String {
+    let (year, month, day) = civil_from_unix(timestamp);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+fn format_axis_value(value: f64) -> String {
+    if value.abs() >= 1000.0 {
+        format!("{:.0}", value)
+    } else if value.abs() >= 1.0 {
+        format!("{:.2}", value)
+    } else {
+        format!("{:.5}", value)
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::compute_metrics;
+    use crate::types::{DiagnosticsSnapshot, Trade};
+
+    fn snapshot(timestamp: i64, equity: f64) -> EquitySnapshot {
+        EquitySnapshot { timestamp, cash: equity, equity, position_value: 0.0, ..Default::default() }
+    }
+
+    #[test]
+    fn test_report_is_self_contained() {
+        let result = BacktestResult {
+            trades: vec![Trade { timestamp: 1_704_067_200, action: Signal::Buy, price: 1.0, size: 100.0, fee: 0.1, liquidation: false, closing: false, remaining: 0.0, realized_pnl: 0.0, slippage: 0.0 }],
+            equity_curve: vec![snapshot(1_704_067_200, 100.0), snapshot(1_706_745_600, 105.0)],
+            diagnostics: vec![DiagnosticsSnapshot { timestamp: 1_704_067_200, values: vec![("atr".to_string(), 0.5)] }],
+            margin_calls: Vec::new(),
+            terminated: None,
+        };
+        let metrics = compute_metrics(&result.equity_curve);
+        let html = render_html_report("ETC <test>", &[], &result, &metrics);
+
+        assert!(html.contains("ETC &lt;test&gt;"));
+        assert!(html.contains("<svg"));
+        assert!(html.contains("<h3>atr</h3>"));
+        assert!(html.contains("5.00% (2024-02)"));
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("<link"));
+        assert!(!html.contains("src="));
+    }
+}
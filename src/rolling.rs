@@ -0,0 +1,226 @@
+// src/rolling.rs
+//
+// O(1) (amortized) rolling-window primitives used by indicators that would
+// otherwise re-scan their whole window on every candle.
+
+use std::collections::VecDeque;
+
+/// Sum of the last `window` values.
+#[derive(Debug, Clone)]
+pub struct RollingSum {
+    window: usize,
+    values: VecDeque<f64>,
+    sum: f64,
+    since_refresh: usize,
+}
+
+impl RollingSum {
+    pub fn new(window: usize) -> Self {
+        let window = window.max(1);
+        Self {
+            window,
+            values: VecDeque::with_capacity(window + 1),
+            sum: 0.0,
+            since_refresh: 0,
+        }
+    }
+
+    pub fn push(&mut self, value: f64) {
+        self.values.push_back(value);
+        self.sum += value;
+        if self.values.len() > self.window {
+            if let Some(old) = self.values.pop_front() {
+                self.sum -= old;
+            }
+            // Re-add from scratch once per window so rounding errors from the
+            // running add/subtract cannot accumulate over long series
+            self.since_refresh += 1;
+            if self.since_refresh >= self.window {
+                self.sum = self.values.iter().sum();
+                self.since_refresh = 0;
+            }
+        }
+    }
+
+    pub fn sum(&self) -> f64 {
+        self.sum
+    }
+
+    /// Mean of the values currently in the window.
+    pub fn mean(&self) -> Option<f64> {
+        if self.values.is_empty() {
+            None
+        } else {
+            Some(self.sum / self.values.len() as f64)
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.values.len() == self.window
+    }
+
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.sum = 0.0;
+        self.since_refresh = 0;
+    }
+}
+
+/// Monotonic deque keeping the extreme of the last `window` values. Each
+/// value is pushed and popped at most once, so updates are amortized O(1).
+#[derive(Debug, Clone)]
+struct MonotonicWindow {
+    window: usize,
+    count: usize,
+    deque: VecDeque<(usize, f64)>,
+    keep_max: bool,
+}
+
+impl MonotonicWindow {
+    fn new(window: usize, keep_max: bool) -> Self {
+        Self {
+            window: window.max(1),
+            count: 0,
+            deque: VecDeque::new(),
+            keep_max,
+        }
+    }
+
+    fn push(&mut self, value: f64) {
+        // Drop values that can never be the extreme again
+        while let Some(&(_, back)) = self.deque.back() {
+            let dominated = if self.keep_max { back <= value } else { back >= value };
+            if dominated {
+                self.deque.pop_back();
+            } else {
+                break;
+            }
+        }
+        self.deque.push_back((self.count, value));
+        self.count += 1;
+
+        while let Some(&(index, _)) = self.deque.front() {
+            if index + self.window < self.count {
+                self.deque.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn get(&self) -> Option<f64> {
+        self.deque.front().map(|&(_, v)| v)
+    }
+
+    fn is_full(&self) -> bool {
+        self.count >= self.window
+    }
+
+    fn clear(&mut self) {
+        self.deque.clear();
+        self.count = 0;
+    }
+}
+
+/// Maximum of the last `window` values.
+#[derive(Debug, Clone)]
+pub struct RollingMax(MonotonicWindow);
+
+impl RollingMax {
+    pub fn new(window: usize) -> Self {
+        Self(MonotonicWindow::new(window, true))
+    }
+
+    pub fn push(&mut self, value: f64) {
+        self.0.push(value);
+    }
+
+    pub fn get(&self) -> Option<f64> {
+        self.0.get()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.0.is_full()
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+/// Minimum of the last `window` values.
+#[derive(Debug, Clone)]
+pub struct RollingMin(MonotonicWindow);
+
+impl RollingMin {
+    pub fn new(window: usize) -> Self {
+        Self(MonotonicWindow::new(window, false))
+    }
+
+    pub fn push(&mut self, value: f64) {
+        self.0.push(value);
+    }
+
+    pub fn get(&self) -> Option<f64> {
+        self.0.get()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.0.is_full()
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indicator::testing::TestRng;
+
+    #[test]
+    fn test_rolling_windows_match_naive() {
+        let mut rng = TestRng::new(42);
+        let data: Vec<f64> = (0..500).map(|_| rng.next_f64() * 100.0).collect();
+        for window in [1, 2, 7, 50] {
+            let mut sum = RollingSum::new(window);
+            let mut max = RollingMax::new(window);
+            let mut min = RollingMin::new(window);
+            for (i, &value) in data.iter().enumerate() {
+                sum.push(value);
+                max.push(value);
+                min.push(value);
+
+                let slice = &data[(i + 1).saturating_sub(window)..=i];
+                let expected_sum: f64 = slice.iter().sum();
+                assert!((sum.sum() - expected_sum).abs() < 1e-9);
+                assert_eq!(max.get(), Some(slice.iter().cloned().fold(f64::MIN, f64::max)));
+                assert_eq!(min.get(), Some(slice.iter().cloned().fold(f64::MAX, f64::min)));
+                assert_eq!(sum.is_full(), i + 1 >= window);
+                assert_eq!(max.is_full(), i + 1 >= window);
+            }
+        }
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut max = RollingMax::new(3);
+        max.push(5.0);
+        max.clear();
+        assert_eq!(max.get(), None);
+        assert!(!max.is_full());
+    }
+}
@@ -0,0 +1,326 @@
+// src/sizing.rs
+//
+// Position sizing policies, kept apart from the strategies: a strategy says
+// when to enter, the sizer how much. Plug one into a run with
+// `TradeModel::with_sizer`.
+
+use return_quant::Returns;
+
+use crate::rolling::RollingSum;
+use crate::strategy::{AtrSmoothing, ATR};
+use crate::types::{Candle, Signal, Trade};
+
+pub trait PositionSizer {
+    /// Notional to commit to a new entry at `price`, given the account equity.
+    /// The trade model caps it at the buying power; 0 skips the entry.
+    fn entry_notional(&self, equity: f64, price: f64) -> f64;
+    /// Called on every candle before the signal is applied.
+    fn update(&mut self, _candle: &Candle) {}
+    /// Called with every fill, including liquidations.
+    fn record_trade(&mut self, _trade: &Trade) {}
+}
+
+/// A fixed share of the equity per entry, e.g. `0.25`.
+#[derive(Debug, Clone)]
+pub struct FixedFractional {
+    pub fraction: f64,
+}
+
+impl FixedFractional {
+    pub fn new(fraction: f64) -> Self {
+        Self { fraction: fraction.max(0.0) }
+    }
+}
+
+impl PositionSizer for FixedFractional {
+    fn entry_notional(&self, equity: f64, _price: f64) -> f64 {
+        equity * self.fraction
+    }
+}
+
+/// The same amount of quote currency per entry.
+#[derive(Debug, Clone)]
+pub struct FixedNotional {
+    pub notional: f64,
+}
+
+impl FixedNotional {
+    pub fn new(notional: f64) -> Self {
+        Self { notional: notional.max(0.0) }
+    }
+}
+
+impl PositionSizer for FixedNotional {
+    fn entry_notional(&self, _equity: f64, _price: f64) -> f64 {
+        self.notional
+    }
+}
+
+/// Kelly criterion `W - (1 - W) / R` estimated from the closed fills so far
+/// (win rate `W`, average win over average loss `R`), scaled by `multiplier`
+/// (e.g. `0.5` for half Kelly). `initial_fraction` is used until `min_trades`
+/// closing fills have been seen.
+#[derive(Debug, Clone)]
+pub struct Kelly {
+    pub multiplier: f64,
+    pub min_trades: usize,
+    pub initial_fraction: f64,
+    pub max_fraction: f64,
+    wins: Vec<f64>,
+    losses: Vec<f64>,
+}
+
+impl Kelly {
+    pub fn new(multiplier: f64) -> Self {
+        Self {
+            multiplier,
+            min_trades: 20,
+            initial_fraction: 0.1,
+            max_fraction: 1.0,
+            wins: Vec::new(),
+            losses: Vec::new(),
+        }
+    }
+
+    pub fn with_min_trades(mut self, min_trades: usize) -> Self {
+        self.min_trades = min_trades;
+        self
+    }
+
+    pub fn with_initial_fraction(mut self, fraction: f64) -> Self {
+        self.initial_fraction = fraction;
+        self
+    }
+
+    pub fn with_max_fraction(mut self, fraction: f64) -> Self {
+        self.max_fraction = fraction.max(0.0);
+        self
+    }
+
+    /// Current Kelly fraction of equity, after the multiplier.
+    pub fn fraction(&self) -> f64 {
+        if self.wins.len() + self.losses.len() < self.min_trades {
+            return self.initial_fraction;
+        }
+        if self.losses.is_empty() {
+            return self.max_fraction;
+        }
+        if self.wins.is_empty() {
+            return 0.0;
+        }
+        let win_rate = self.wins.len() as f64 / (self.wins.len() + self.losses.len()) as f64;
+        let payoff = sharp_ratio::average(&self.wins) / sharp_ratio::average(&self.losses);
+        let kelly = win_rate - (1.0 - win_rate) / payoff;
+        (kelly * self.multiplier).clamp(0.0, self.max_fraction)
+    }
+}
+
+impl PositionSizer for Kelly {
+    fn entry_notional(&self, equity: f64, _price: f64) -> f64 {
+        equity * self.fraction()
+    }
+
+    fn record_trade(&mut self, trade: &Trade) {
+        if !trade.closing {
+            return;
+        }
+        let notional = trade.price * trade.size;
+        let cost = match trade.action {
+            Signal::Sell => notional - trade.realized_pnl,
+            _ => notional + trade.realized_pnl,
+        };
+        if cost <= 0.0 {
+            return;
+        }
+        let ret = trade.realized_pnl / cost;
+        if ret > 0.0 {
+            self.wins.push(ret);
+        } else {
+            self.losses.push(-ret);
+        }
+    }
+}
+
+/// Risk a fixed share of equity per trade, with the stop assumed
+/// `multiplier` ATRs away: `units = equity * risk / (ATR * multiplier)`.
+/// No entries until the ATR is ready. Uncapped unless `with_max_fraction`
+/// limits the notional to a share of equity.
+#[derive(Clone)]
+pub struct AtrRisk {
+    pub risk_fraction: f64,
+    pub multiplier: f64,
+    pub max_fraction: f64,
+    atr: ATR,
+}
+
+impl AtrRisk {
+    pub fn new(risk_fraction: f64, period: usize, multiplier: f64) -> Self {
+        Self { risk_fraction, multiplier, max_fraction: f64::INFINITY, atr: ATR::new(period) }
+    }
+
+    pub fn with_max_fraction(mut self, fraction: f64) -> Self {
+        self.max_fraction = fraction.max(0.0);
+        self
+    }
+
+    pub fn with_smoothing(mut self, smoothing: AtrSmoothing) -> Self {
+        self.atr = self.atr.with_smoothing(smoothing);
+        self
+    }
+}
+
+impl PositionSizer for AtrRisk {
+    fn entry_notional(&self, equity: f64, price: f64) -> f64 {
+        match self.atr.get() {
+            Some(atr) if atr > 0.0 => (equity * self.risk_fraction / (atr * self.multiplier) * price).min(equity * self.max_fraction),
+            _ => 0.0,
+        }
+    }
+
+    fn update(&mut self, candle: &Candle) {
+        self.atr.update(candle);
+    }
+}
+
+/// Scale the notional so the position's volatility matches
+/// `target_volatility` (per candle): `equity * target / realized`, with the
+/// realized volatility of the last `lookback` close-to-close returns.
+/// No entries until `lookback` returns are available.
+#[derive(Debug, Clone)]
+pub struct VolatilityParity {
+    pub target_volatility: f64,
+    pub lookback: usize,
+    returns: Returns,
+    sum: RollingSum,
+    sum_sq: RollingSum,
+}
+
+impl VolatilityParity {
+    pub fn new(target_volatility: f64, lookback: usize) -> Self {
+        let lookback = lookback.max(2);
+        Self {
+            target_volatility,
+            lookback,
+            returns: Returns::new(),
+            sum: RollingSum::new(lookback),
+            sum_sq: RollingSum::new(lookback),
+        }
+    }
+
+    /// Sample standard deviation of the returns in the window.
+    fn realized_volatility(&self) -> f64 {
+        let n = self.sum.len() as f64;
+        let variance = (self.sum_sq.sum() - self.sum.sum() * self.sum.sum() / n) / (n - 1.0);
+        variance.max(0.0).sqrt()
+    }
+}
+
+impl PositionSizer for VolatilityParity {
+    fn entry_notional(&self, equity: f64, _price: f64) -> f64 {
+        if !self.sum.is_full() {
+            return 0.0;
+        }
+        let realized = self.realized_volatility();
+        if realized > 0.0 { equity * self.target_volatility / realized } else { 0.0 }
+    }
+
+    fn update(&mut self, candle: &Candle) {
+        if let Some(ret) = self.returns.next(candle.close) {
+            self.sum.push(ret);
+            self.sum_sq.push(ret * ret);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trade_model::TradeModel;
+
+    fn candle(timestamp: i64, low: f64, high: f64, close: f64) -> Candle {
+        Candle { timestamp, open: close, high, low, close, volume: 1.0, period: 60.0 }
+    }
+
+    fn closing(action: Signal, price: f64, realized_pnl: f64) -> Trade {
+        Trade { timestamp: 0, action, price, size: 1.0, fee: 0.0, liquidation: false, closing: true, remaining: 0.0, realized_pnl, slippage: 0.0 }
+    }
+
+    #[test]
+    fn test_fixed_sizers() {
+        assert_eq!(FixedFractional::new(0.25).entry_notional(1000.0, 50.0), 250.0);
+        assert_eq!(FixedNotional::new(300.0).entry_notional(1000.0, 50.0), 300.0);
+    }
+
+    #[test]
+    fn test_kelly_fraction() {
+        // A negative or NaN cap is sanitized rather than panicking in `fraction`
+        for max_fraction in [-1.0, f64::NAN] {
+            let mut kelly = Kelly::new(0.5).with_min_trades(2).with_max_fraction(max_fraction);
+            kelly.record_trade(&closing(Signal::Sell, 110.0, 10.0));
+            kelly.record_trade(&closing(Signal::Buy, 105.0, -5.0));
+            assert_eq!(kelly.fraction(), 0.0);
+        }
+
+        let mut kelly = Kelly::new(0.5).with_min_trades(4).with_initial_fraction(0.05);
+        assert_eq!(kelly.entry_notional(1000.0, 1.0), 50.0);
+        // Wins of +10% (long) and losses of -5% (short): W = 0.5, R = 2, Kelly 0.25
+        for _ in 0..2 {
+            kelly.record_trade(&closing(Signal::Sell, 110.0, 10.0));
+            kelly.record_trade(&closing(Signal::Buy, 105.0, -5.0));
+        }
+        // Entries are not part of the sample
+        kelly.record_trade(&Trade { closing: false, ..closing(Signal::Buy, 100.0, 0.0) });
+        assert!((kelly.fraction() - 0.125).abs() < 1e-12);
+        // A break-even exit is: W = 0.4, R = 3, Kelly 0.2
+        kelly.record_trade(&closing(Signal::Sell, 100.0, 0.0));
+        assert!((kelly.fraction() - 0.1).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_atr_and_volatility_sizers_wait_for_data() {
+        let mut atr = AtrRisk::new(0.01, 2, 2.0);
+        let mut parity = VolatilityParity::new(0.01, 2);
+        for (i, close) in [100.0, 102.0, 100.0].into_iter().enumerate() {
+            if i < 2 {
+                assert_eq!(parity.entry_notional(1000.0, close), 0.0);
+            }
+            if i < 1 {
+                assert_eq!(atr.entry_notional(1000.0, close), 0.0);
+            }
+            let c = candle(i as i64, close - 1.0, close + 1.0, close);
+            atr.update(&c);
+            parity.update(&c);
+        }
+        // Risking 1% of equity on a stop two ATRs away
+        let atr_value = atr.atr.get().unwrap();
+        assert!((atr.entry_notional(1000.0, 100.0) - 1000.0 * 0.01 / (atr_value * 2.0) * 100.0).abs() < 1e-9);
+        // Returns of +2% and about -1.96% give a realized volatility near 2.8%
+        let expected = 1000.0 * 0.01 / sharp_ratio::std_deviation(&[0.02, 100.0 / 102.0 - 1.0]);
+        assert!((parity.entry_notional(1000.0, 100.0) - expected).abs() < 1e-9);
+        assert!(expected < 1000.0);
+    }
+
+    #[test]
+    fn test_volatility_parity_window_slides() {
+        let closes = [100.0, 103.0, 99.0, 104.0, 104.5, 101.0];
+        let mut parity = VolatilityParity::new(0.01, 3);
+        for (i, &close) in closes.iter().enumerate() {
+            parity.update(&candle(i as i64, close, close, close));
+        }
+        let returns: Vec<f64> = closes.windows(2).map(|w| w[1] / w[0] - 1.0).collect();
+        let expected = 1000.0 * 0.01 / sharp_ratio::std_deviation(&returns[returns.len() - 3..]);
+        assert!((parity.entry_notional(1000.0, 101.0) - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_trade_model_uses_sizer() {
+        let mut model = TradeModel::new(1000.0, 0.0, 0.0, 1.0).with_sizer(Box::new(FixedNotional::new(250.0)));
+        let trade = model.apply(Signal::Buy, &candle(0, 100.0, 100.0, 100.0)).unwrap();
+        assert_eq!(trade.size, 2.5);
+        assert_eq!(model.cash, 750.0);
+
+        // More than the buying power is capped at the cash left
+        let mut model = TradeModel::new(1000.0, 0.0, 0.0, 1.0).with_sizer(Box::new(FixedFractional::new(3.0)));
+        assert_eq!(model.apply(Signal::Buy, &candle(0, 100.0, 100.0, 100.0)).unwrap().size, 10.0);
+    }
+}
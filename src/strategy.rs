@@ -5,19 +5,82 @@ pub mod momentum;
 pub mod atr;
 pub mod atr_breakout;
 pub mod volatility_targeting;
+pub mod rsi;
+pub mod rsi_reversion;
+pub mod macd;
+pub mod macd_cross;
+pub mod bollinger;
+pub mod bollinger_reversion;
+pub mod vwap;
+pub mod obv;
+pub mod mfi;
+pub mod volume_spike;
+pub mod volume_breakout;
+pub mod donchian;
+pub mod keltner;
+pub mod adx;
+pub mod supertrend;
+pub mod turtle;
+pub mod combinators;
+pub mod crossover;
+pub mod registry;
 
+use crate::sizing::PositionSizer;
 use crate::types::{Candle, Signal};
-use std::any::Any;
 
 pub trait Strategy {
     fn next(&mut self, candle: &Candle) -> Signal;
-    fn as_any(&self) -> &dyn Any;
+    /// Clear all state built up from previous candles, keeping the configuration.
+    fn reset(&mut self);
+    /// Boxed copy of this strategy, including its current state.
+    fn box_clone(&self) -> Box<dyn Strategy>;
+    /// Named indicator values and internal state after the latest candle,
+    /// e.g. `("short_ema", 101.3)`. Nested strategies prefix their names.
+    fn diagnostics(&self) -> Vec<(String, f64)> {
+        Vec::new()
+    }
+    /// Number of candles needed before the indicators behind the signals are
+    /// ready; every signal before that is `Hold`.
+    fn warm_up(&self) -> usize {
+        0
+    }
+    /// Position sizer the strategy's rules call for, if any. Runs install it
+    /// on the trade model unless one is already set.
+    fn sizer(&self) -> Option<Box<dyn PositionSizer>> {
+        None
+    }
 }
 
+impl Clone for Box<dyn Strategy> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
+
+pub use indicator::Indicator;
 pub use ema_cross::EmaCross;
 pub use sma_cross::SmaCross;
 pub use mean_reversion::MeanReversion;
 pub use momentum::Momentum;
-pub use atr::ATR;
+pub use atr::{AtrSmoothing, ATR};
 pub use atr_breakout::ATRBreakout;
-pub use volatility_targeting::VolatilityTargeting;
\ No newline at end of file
+pub use volatility_targeting::VolatilityTargeting;
+pub use rsi::Rsi;
+pub use rsi_reversion::RsiReversion;
+pub use macd::{Macd, MacdValue};
+pub use macd_cross::MacdCross;
+pub use bollinger::{Bands, BollingerBands};
+pub use bollinger_reversion::BollingerReversion;
+pub use vwap::{Vwap, VwapAnchor};
+pub use obv::Obv;
+pub use mfi::Mfi;
+pub use volume_spike::VolumeSpike;
+pub use volume_breakout::VolumeBreakout;
+pub use donchian::{Channel, DonchianChannel};
+pub use keltner::KeltnerChannel;
+pub use adx::{Adx, DmiValue};
+pub use supertrend::{Supertrend, SupertrendValue};
+pub use turtle::Turtle;
+pub use crossover::{CrossDetector, CrossMode};
+pub use combinators::{AllOf, AnyOf, Cooldown, Invert, MajorityVote, RegimeFilter};
+pub use registry::{ParamKind, ParamSpec, ParamValue, Params, Registry, RegistryError, StrategySpec};
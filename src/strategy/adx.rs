@@ -0,0 +1,157 @@
+use indicator::Indicator;
+
+use crate::types::Candle;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DmiValue {
+    pub plus_di: f64,
+    pub minus_di: f64,
+    pub adx: f64,
+}
+
+/// Wilder's Directional Movement Index (+DI / -DI) and Average Directional Index.
+///
+/// +DI and -DI are available after `period` candles following the first one;
+/// ADX (a Wilder average of DX) needs another `period - 1`.
+#[derive(Debug, Clone)]
+pub struct Adx {
+    period: usize,
+    prev: Option<(f64, f64, f64)>,
+    count: usize,
+    tr: f64,
+    plus_dm: f64,
+    minus_dm: f64,
+    dx_count: usize,
+    adx: f64,
+    value: Option<DmiValue>,
+}
+
+impl Adx {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period: period.max(1),
+            prev: None,
+            count: 0,
+            tr: 0.0,
+            plus_dm: 0.0,
+            minus_dm: 0.0,
+            dx_count: 0,
+            adx: 0.0,
+            value: None,
+        }
+    }
+
+    pub fn update(&mut self, candle: &Candle) -> Option<DmiValue> {
+        let (prev_high, prev_low, prev_close) = self.prev.replace((candle.high, candle.low, candle.close))?;
+
+        let up_move = candle.high - prev_high;
+        let down_move = prev_low - candle.low;
+        let plus_dm = if up_move > down_move && up_move > 0.0 { up_move } else { 0.0 };
+        let minus_dm = if down_move > up_move && down_move > 0.0 { down_move } else { 0.0 };
+        let tr = (candle.high - candle.low)
+            .max((candle.high - prev_close).abs())
+            .max((candle.low - prev_close).abs());
+
+        let n = self.period as f64;
+        self.count += 1;
+        if self.count <= self.period {
+            self.tr += tr / n;
+            self.plus_dm += plus_dm / n;
+            self.minus_dm += minus_dm / n;
+            if self.count < self.period {
+                return None;
+            }
+        } else {
+            self.tr = wilder(self.tr, tr, n);
+            self.plus_dm = wilder(self.plus_dm, plus_dm, n);
+            self.minus_dm = wilder(self.minus_dm, minus_dm, n);
+        }
+
+        let (plus_di, minus_di) = if self.tr > 0.0 {
+            (100.0 * self.plus_dm / self.tr, 100.0 * self.minus_dm / self.tr)
+        } else {
+            (0.0, 0.0)
+        };
+        let di_sum = plus_di + minus_di;
+        let dx = if di_sum > 0.0 { 100.0 * (plus_di - minus_di).abs() / di_sum } else { 0.0 };
+
+        self.dx_count += 1;
+        if self.dx_count <= self.period {
+            self.adx += dx / n;
+            if self.dx_count < self.period {
+                return None;
+            }
+        } else {
+            self.adx = wilder(self.adx, dx, n);
+        }
+
+        self.value = Some(DmiValue { plus_di, minus_di, adx: self.adx });
+        self.value
+    }
+
+    pub fn get(&self) -> Option<DmiValue> {
+        self.value
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new(self.period);
+    }
+}
+
+fn wilder(prev: f64, value: f64, n: f64) -> f64 {
+    (prev * (n - 1.0) + value) / n
+}
+
+impl Indicator for Adx {
+    type Input = Candle;
+    type Output = DmiValue;
+
+    fn next(&mut self, input: Candle) -> Option<DmiValue> {
+        self.update(&input)
+    }
+
+    fn value(&self) -> Option<DmiValue> {
+        self.get()
+    }
+
+    fn reset(&mut self) {
+        Adx::reset(self)
+    }
+
+    /// First candle, `period` for +DI/-DI, then `period - 1` more for ADX.
+    fn warm_up(&self) -> usize {
+        2 * self.period
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adx_strong_trends() {
+        let candle = |i: f64| Candle { timestamp: 0, open: i, high: i + 1.0, low: i - 1.0, close: i, volume: 1.0, period: 1.0 };
+
+        let mut up = Adx::new(5);
+        let outputs: Vec<Option<DmiValue>> = (0..12).map(|i| up.update(&candle(i as f64))).collect();
+        assert!(outputs[..9].iter().all(Option::is_none));
+        let value = outputs[11].unwrap();
+        assert!(value.plus_di > value.minus_di);
+        assert!((value.adx - 100.0).abs() < 1e-9);
+
+        let mut down = Adx::new(5);
+        let last = (0..12).map(|i| down.update(&candle(-(i as f64)))).last().flatten().unwrap();
+        assert!(last.minus_di > last.plus_di);
+    }
+
+    #[test]
+    fn test_warm_up_matches_first_output() {
+        let candles: Vec<Candle> = (0..20)
+            .map(|i| Candle { timestamp: 0, open: 1.0, high: 2.0 + i as f64, low: 0.5, close: 1.5, volume: 1.0, period: 1.0 })
+            .collect();
+        let mut adx = Adx::new(4);
+        let outputs = indicator::run(&mut adx, candles);
+        assert_eq!(outputs.iter().position(Option::is_some), Some(adx.warm_up() - 1));
+        assert!(adx.is_ready());
+    }
+}
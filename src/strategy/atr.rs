@@ -1,35 +1,99 @@
+use ema::Ema;
+use indicator::Indicator;
+
+use crate::rolling::RollingSum;
 use crate::types::Candle;
 
+/// How true ranges are averaged into the ATR.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AtrSmoothing {
+    /// Plain mean of the last `period` true ranges.
+    Simple,
+    /// Wilder's RMA: seeded with the simple mean, then `(prev * (n - 1) + tr) / n`.
+    Wilder,
+    /// Exponential moving average with `alpha = 2 / (n + 1)`.
+    Exponential,
+}
+
+impl AtrSmoothing {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "simple" => Some(AtrSmoothing::Simple),
+            "wilder" => Some(AtrSmoothing::Wilder),
+            "exponential" => Some(AtrSmoothing::Exponential),
+            _ => None,
+        }
+    }
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone)]
 pub struct ATR {
     period: usize,
-    tr_values: Vec<f64>,
+    smoothing: AtrSmoothing,
+    tr_sum: RollingSum,
+    ema: Ema,
+    count: usize,
+    value: Option<f64>,
     prev_close: Option<f64>,
 }
 
 impl ATR {
     pub fn new(period: usize) -> Self {
+        let period = period.max(1);
         Self {
             period,
-            tr_values: Vec::with_capacity(period),
+            smoothing: AtrSmoothing::Simple,
+            tr_sum: RollingSum::new(period),
+            ema: Ema::new(period),
+            count: 0,
+            value: None,
             prev_close: None,
         }
     }
 
+    pub fn with_smoothing(mut self, smoothing: AtrSmoothing) -> Self {
+        self.smoothing = smoothing;
+        self
+    }
+
     pub fn update(&mut self, candle: &Candle) -> Option<f64> {
         let tr = self.true_range(candle);
-        self.tr_values.push(tr);
+        self.prev_close = Some(candle.close);
+        self.count += 1;
+        let n = self.period as f64;
 
-        if self.tr_values.len() > self.period {
-            self.tr_values.remove(0);
-        }
+        self.value = match self.smoothing {
+            AtrSmoothing::Simple => {
+                self.tr_sum.push(tr);
+                if self.tr_sum.is_full() { Some(self.tr_sum.sum() / n) } else { None }
+            }
+            AtrSmoothing::Wilder => match self.value {
+                Some(prev) => Some((prev * (n - 1.0) + tr) / n),
+                None => {
+                    self.tr_sum.push(tr);
+                    if self.tr_sum.is_full() { Some(self.tr_sum.sum() / n) } else { None }
+                }
+            },
+            AtrSmoothing::Exponential => {
+                let ema = self.ema.next(tr);
+                if self.count >= self.period { Some(ema) } else { None }
+            }
+        };
 
-        self.prev_close = Some(candle.close);
+        self.value // None until `period` candles are in
+    }
 
-        if self.tr_values.len() < self.period {
-            None // not enough data yet
-        } else {
-            Some(self.tr_values.iter().sum::<f64>() / self.period as f64)
-        }
+    pub fn get(&self) -> Option<f64> {
+        self.value
+    }
+
+    pub fn reset(&mut self) {
+        self.tr_sum.clear();
+        self.ema.reset();
+        self.count = 0;
+        self.value = None;
+        self.prev_close = None;
     }
 
     fn true_range(&self, candle: &Candle) -> f64 {
@@ -40,3 +104,65 @@ impl ATR {
         high_low.max(high_close).max(low_close)
     }
 }
+
+impl Indicator for ATR {
+    type Input = Candle;
+    type Output = f64;
+
+    fn next(&mut self, input: Candle) -> Option<f64> {
+        self.update(&input)
+    }
+
+    fn value(&self) -> Option<f64> {
+        self.value
+    }
+
+    fn reset(&mut self) {
+        ATR::reset(self)
+    }
+
+    fn warm_up(&self) -> usize {
+        self.period
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(high: f64, low: f64, close: f64) -> Candle {
+        Candle { timestamp: 0, open: close, high, low, close, volume: 1.0, period: 1.0 }
+    }
+
+    #[test]
+    fn test_atr_smoothing_methods() {
+        // True ranges: 2, 4, 6, 2
+        let candles = [candle(11.0, 9.0, 10.0), candle(12.0, 8.0, 10.0), candle(13.0, 7.0, 10.0), candle(11.0, 9.0, 10.0)];
+
+        let mut simple = ATR::new(3);
+        let values: Vec<Option<f64>> = candles.iter().map(|c| simple.update(c)).collect();
+        assert_eq!(values, vec![None, None, Some(4.0), Some(4.0)]);
+
+        let mut wilder = ATR::new(3).with_smoothing(AtrSmoothing::Wilder);
+        let values: Vec<Option<f64>> = candles.iter().map(|c| wilder.update(c)).collect();
+        assert_eq!(values, vec![None, None, Some(4.0), Some((4.0 * 2.0 + 2.0) / 3.0)]);
+
+        let mut exponential = ATR::new(3).with_smoothing(AtrSmoothing::Exponential);
+        let values: Vec<Option<f64>> = candles.iter().map(|c| exponential.update(c)).collect();
+        // alpha = 0.5: 2 -> 3 -> 4.5 -> 3.25
+        assert_eq!(values, vec![None, None, Some(4.5), Some(3.25)]);
+    }
+
+    #[test]
+    fn test_warm_up_matches_first_output() {
+        let candles: Vec<Candle> = (0..10).map(|i| candle(11.0 + i as f64, 9.0, 10.0)).collect();
+        for smoothing in [AtrSmoothing::Simple, AtrSmoothing::Wilder, AtrSmoothing::Exponential] {
+            let mut atr = ATR::new(4).with_smoothing(smoothing);
+            let outputs = indicator::run(&mut atr, candles.iter().copied());
+            assert_eq!(outputs.iter().position(Option::is_some), Some(atr.warm_up() - 1));
+
+            Indicator::reset(&mut atr);
+            assert!(!atr.is_ready());
+        }
+    }
+}
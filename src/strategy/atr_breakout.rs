@@ -1,59 +1,82 @@
 use crate::types::{Candle, Signal};
-use super::{Strategy, ATR};
+use indicator::Indicator;
+use super::{AtrSmoothing, DonchianChannel, Strategy, ATR};
 
+#[derive(Clone)]
 pub struct ATRBreakout {
     atr: ATR,
-    lookback: usize,
-    closes: Vec<f64>,
+    channel: DonchianChannel,
     atr_multiplier: f64,
+    last_atr: Option<f64>,
 }
 
 impl ATRBreakout {
     pub fn new(lookback: usize, atr_period: usize, atr_multiplier: f64) -> Self {
         Self {
             atr: ATR::new(atr_period),
-            lookback,
-            closes: Vec::with_capacity(lookback),
+            channel: DonchianChannel::new(lookback),
             atr_multiplier,
+            last_atr: None,
         }
     }
+
+    pub fn with_atr_smoothing(mut self, smoothing: AtrSmoothing) -> Self {
+        self.atr = self.atr.with_smoothing(smoothing);
+        self
+    }
 }
 
 impl Strategy for ATRBreakout {
     fn next(&mut self, candle: &Candle) -> Signal {
         // Update ATR
         let atr_value = self.atr.update(candle);
+        self.last_atr = atr_value;
 
-        // Only act when we have enough data
-        if atr_value.is_none() || self.closes.len() < self.lookback {
-            self.closes.push(candle.close);
-            return Signal::Hold;
-        }
+        // Range of the previous `lookback` closes, before adding this candle
+        let range = self.channel.get();
+        self.channel.update(candle.close, candle.close);
 
-        let atr = atr_value.unwrap();
-        let highest = self.closes.iter().cloned().fold(f64::MIN, f64::max);
-        let lowest = self.closes.iter().cloned().fold(f64::MAX, f64::min);
+        // Only act when we have enough data
+        let (atr, range) = match (atr_value, range) {
+            (Some(atr), Some(range)) => (atr, range),
+            _ => return Signal::Hold,
+        };
 
         let volatility_ok = atr > self.atr_multiplier * candle.close;
 
-        let signal = if candle.close > highest && volatility_ok {
+        if candle.close > range.upper && volatility_ok {
             Signal::Buy
-        } else if candle.close < lowest && volatility_ok {
+        } else if candle.close < range.lower && volatility_ok {
             Signal::Sell
         } else {
             Signal::Hold
-        };
-
-        // Now update close history
-        self.closes.push(candle.close);
-        if self.closes.len() > self.lookback {
-            self.closes.remove(0);
         }
-
-        signal
     }
     
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+    fn reset(&mut self) {
+        self.atr.reset();
+        self.channel.reset();
+        self.last_atr = None;
+    }
+
+    fn box_clone(&self) -> Box<dyn Strategy> {
+        Box::new(self.clone())
+    }
+
+    fn warm_up(&self) -> usize {
+        // The channel is compared before the current candle is added
+        self.atr.warm_up().max(self.channel.warm_up() + 1)
+    }
+
+    fn diagnostics(&self) -> Vec<(String, f64)> {
+        let mut values = Vec::new();
+        if let Some(atr) = self.last_atr {
+            values.push(("atr".to_string(), atr));
+        }
+        if let Some(range) = self.channel.get() {
+            values.push(("highest".to_string(), range.upper));
+            values.push(("lowest".to_string(), range.lower));
+        }
+        values
     }
 }
\ No newline at end of file
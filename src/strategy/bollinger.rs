@@ -0,0 +1,91 @@
+use moving_avg::Sma;
+use indicator::Indicator;
+use sharp_ratio::std_deviation;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bands {
+    pub middle: f64,
+    pub upper: f64,
+    pub lower: f64,
+}
+
+/// Bollinger Bands: SMA of the close plus/minus `num_std` standard deviations.
+#[derive(Debug, Clone)]
+pub struct BollingerBands {
+    sma: Sma,
+    num_std: f64,
+    value: Option<Bands>,
+}
+
+impl BollingerBands {
+    pub fn new(period: usize, num_std: f64) -> Self {
+        Self {
+            sma: Sma::new(period),
+            num_std,
+            value: None,
+        }
+    }
+
+    pub fn next(&mut self, price: f64) -> Option<Bands> {
+        self.sma.next(price);
+        let middle = self.sma.get()?;
+        let window: Vec<f64> = self.sma.values.iter().copied().collect();
+        let width = self.num_std * std_deviation(&window);
+
+        self.value = Some(Bands {
+            middle,
+            upper: middle + width,
+            lower: middle - width,
+        });
+        self.value
+    }
+
+    pub fn get(&self) -> Option<Bands> {
+        self.value
+    }
+
+    pub fn reset(&mut self) {
+        self.sma.reset();
+        self.value = None;
+    }
+}
+
+impl Indicator for BollingerBands {
+    type Input = f64;
+    type Output = Bands;
+
+    fn next(&mut self, input: f64) -> Option<Bands> {
+        BollingerBands::next(self, input)
+    }
+
+    fn value(&self) -> Option<Bands> {
+        self.get()
+    }
+
+    fn reset(&mut self) {
+        BollingerBands::reset(self)
+    }
+
+    fn warm_up(&self) -> usize {
+        self.sma.window
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bollinger_bands() {
+        let mut bands = BollingerBands::new(4, 2.0);
+        assert_eq!(bands.next(1.0), None);
+        assert_eq!(bands.next(2.0), None);
+        assert_eq!(bands.next(3.0), None);
+
+        let b = bands.next(4.0).unwrap();
+        let std = std_deviation(&[1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(b.middle, 2.5);
+        assert!((b.upper - (2.5 + 2.0 * std)).abs() < 1e-12);
+        assert!((b.lower - (2.5 - 2.0 * std)).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,50 @@
+use crate::types::{Candle, Signal};
+use indicator::Indicator;
+use super::{BollingerBands, Strategy};
+
+/// Buys when the close falls below the lower band and sells when it rises above the upper band.
+#[derive(Clone)]
+pub struct BollingerReversion {
+    pub bands: BollingerBands,
+}
+
+impl BollingerReversion {
+    pub fn new(period: usize, num_std: f64) -> Self {
+        Self {
+            bands: BollingerBands::new(period, num_std),
+        }
+    }
+}
+
+impl Strategy for BollingerReversion {
+    fn next(&mut self, candle: &Candle) -> Signal {
+        match self.bands.next(candle.close) {
+            Some(b) if candle.close < b.lower => Signal::Buy,
+            Some(b) if candle.close > b.upper => Signal::Sell,
+            _ => Signal::Hold,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.bands.reset();
+    }
+
+    fn box_clone(&self) -> Box<dyn Strategy> {
+        Box::new(self.clone())
+    }
+
+    fn warm_up(&self) -> usize {
+        self.bands.warm_up()
+    }
+
+    fn diagnostics(&self) -> Vec<(String, f64)> {
+        match self.bands.get() {
+            Some(b) => vec![
+                ("middle".to_string(), b.middle),
+                ("upper".to_string(), b.upper),
+                ("lower".to_string(), b.lower),
+            ],
+            None => Vec::new(),
+        }
+    }
+}
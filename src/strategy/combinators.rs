@@ -0,0 +1,403 @@
+use crate::types::{Candle, Signal};
+use super::Strategy;
+
+/// Run every strategy on the candle (so all of them keep their state up to
+/// date) and collect the signals.
+fn next_all(strategies: &mut [Box<dyn Strategy>], candle: &Candle) -> Vec<Signal> {
+    strategies.iter_mut().map(|s| s.next(candle)).collect()
+}
+
+fn count(signals: &[Signal], wanted: Signal) -> usize {
+    signals.iter().filter(|&&s| s == wanted).count()
+}
+
+fn prefixed(prefix: &str, strategy: &dyn Strategy) -> Vec<(String, f64)> {
+    strategy
+        .diagnostics()
+        .into_iter()
+        .map(|(name, value)| (format!("{}.{}", prefix, name), value))
+        .collect()
+}
+
+fn indexed_diagnostics(strategies: &[Box<dyn Strategy>]) -> Vec<(String, f64)> {
+    strategies
+        .iter()
+        .enumerate()
+        .flat_map(|(i, s)| prefixed(&i.to_string(), s.as_ref()))
+        .collect()
+}
+
+fn signal_value(signal: Signal) -> f64 {
+    match signal {
+        Signal::Buy => 1.0,
+        Signal::Sell => -1.0,
+        Signal::Hold => 0.0,
+    }
+}
+
+/// AND: emits `Buy`/`Sell` only when every strategy emits it on the same candle.
+#[derive(Clone)]
+pub struct AllOf {
+    pub strategies: Vec<Box<dyn Strategy>>,
+}
+
+impl AllOf {
+    pub fn new(strategies: Vec<Box<dyn Strategy>>) -> Self {
+        Self { strategies }
+    }
+}
+
+impl Strategy for AllOf {
+    fn next(&mut self, candle: &Candle) -> Signal {
+        let signals = next_all(&mut self.strategies, candle);
+        if signals.is_empty() {
+            Signal::Hold
+        } else if count(&signals, Signal::Buy) == signals.len() {
+            Signal::Buy
+        } else if count(&signals, Signal::Sell) == signals.len() {
+            Signal::Sell
+        } else {
+            Signal::Hold
+        }
+    }
+
+    fn reset(&mut self) {
+        self.strategies.iter_mut().for_each(|s| s.reset());
+    }
+
+    fn box_clone(&self) -> Box<dyn Strategy> {
+        Box::new(self.clone())
+    }
+
+    fn warm_up(&self) -> usize {
+        self.strategies.iter().map(|s| s.warm_up()).max().unwrap_or(0)
+    }
+
+    fn diagnostics(&self) -> Vec<(String, f64)> {
+        indexed_diagnostics(&self.strategies)
+    }
+}
+
+/// OR: emits `Buy`/`Sell` when at least one strategy does and none disagrees.
+#[derive(Clone)]
+pub struct AnyOf {
+    pub strategies: Vec<Box<dyn Strategy>>,
+}
+
+impl AnyOf {
+    pub fn new(strategies: Vec<Box<dyn Strategy>>) -> Self {
+        Self { strategies }
+    }
+}
+
+impl Strategy for AnyOf {
+    fn next(&mut self, candle: &Candle) -> Signal {
+        let signals = next_all(&mut self.strategies, candle);
+        match (count(&signals, Signal::Buy), count(&signals, Signal::Sell)) {
+            (buys, 0) if buys > 0 => Signal::Buy,
+            (0, sells) if sells > 0 => Signal::Sell,
+            _ => Signal::Hold,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.strategies.iter_mut().for_each(|s| s.reset());
+    }
+
+    fn box_clone(&self) -> Box<dyn Strategy> {
+        Box::new(self.clone())
+    }
+
+    fn warm_up(&self) -> usize {
+        self.strategies.iter().map(|s| s.warm_up()).max().unwrap_or(0)
+    }
+
+    fn diagnostics(&self) -> Vec<(String, f64)> {
+        indexed_diagnostics(&self.strategies)
+    }
+}
+
+/// Emits `Buy`/`Sell` when more than half of the strategies agree on it.
+#[derive(Clone)]
+pub struct MajorityVote {
+    pub strategies: Vec<Box<dyn Strategy>>,
+    last_votes: (usize, usize),
+}
+
+impl MajorityVote {
+    pub fn new(strategies: Vec<Box<dyn Strategy>>) -> Self {
+        Self { strategies, last_votes: (0, 0) }
+    }
+}
+
+impl Strategy for MajorityVote {
+    fn next(&mut self, candle: &Candle) -> Signal {
+        let signals = next_all(&mut self.strategies, candle);
+        let buys = count(&signals, Signal::Buy);
+        let sells = count(&signals, Signal::Sell);
+        self.last_votes = (buys, sells);
+
+        let majority = signals.len() / 2;
+        if buys > majority {
+            Signal::Buy
+        } else if sells > majority {
+            Signal::Sell
+        } else {
+            Signal::Hold
+        }
+    }
+
+    fn reset(&mut self) {
+        self.strategies.iter_mut().for_each(|s| s.reset());
+        self.last_votes = (0, 0);
+    }
+
+    fn box_clone(&self) -> Box<dyn Strategy> {
+        Box::new(self.clone())
+    }
+
+    fn warm_up(&self) -> usize {
+        self.strategies.iter().map(|s| s.warm_up()).max().unwrap_or(0)
+    }
+
+    fn diagnostics(&self) -> Vec<(String, f64)> {
+        let mut values = vec![
+            ("buy_votes".to_string(), self.last_votes.0 as f64),
+            ("sell_votes".to_string(), self.last_votes.1 as f64),
+        ];
+        values.extend(indexed_diagnostics(&self.strategies));
+        values
+    }
+}
+
+/// Only lets entries through while a filter strategy agrees.
+///
+/// The regime is the filter's most recent non-`Hold` signal. `Buy` from the
+/// signal strategy passes only in a `Buy` regime; `Sell` always passes so an
+/// open position can still be closed after the regime flips.
+#[derive(Clone)]
+pub struct RegimeFilter {
+    pub signal_strategy: Box<dyn Strategy>,
+    pub filter: Box<dyn Strategy>,
+    regime: Signal,
+}
+
+impl RegimeFilter {
+    pub fn new(signal_strategy: Box<dyn Strategy>, filter: Box<dyn Strategy>) -> Self {
+        Self { signal_strategy, filter, regime: Signal::Hold }
+    }
+}
+
+impl Strategy for RegimeFilter {
+    fn next(&mut self, candle: &Candle) -> Signal {
+        let filter_signal = self.filter.next(candle);
+        if filter_signal != Signal::Hold {
+            self.regime = filter_signal;
+        }
+
+        match self.signal_strategy.next(candle) {
+            Signal::Buy if self.regime == Signal::Buy => Signal::Buy,
+            Signal::Sell => Signal::Sell,
+            _ => Signal::Hold,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.signal_strategy.reset();
+        self.filter.reset();
+        self.regime = Signal::Hold;
+    }
+
+    fn box_clone(&self) -> Box<dyn Strategy> {
+        Box::new(self.clone())
+    }
+
+    fn warm_up(&self) -> usize {
+        self.signal_strategy.warm_up().max(self.filter.warm_up())
+    }
+
+    fn diagnostics(&self) -> Vec<(String, f64)> {
+        let mut values = vec![("regime".to_string(), signal_value(self.regime))];
+        values.extend(prefixed("signal", self.signal_strategy.as_ref()));
+        values.extend(prefixed("filter", self.filter.as_ref()));
+        values
+    }
+}
+
+/// Swaps `Buy` and `Sell`.
+#[derive(Clone)]
+pub struct Invert {
+    pub strategy: Box<dyn Strategy>,
+}
+
+impl Invert {
+    pub fn new(strategy: Box<dyn Strategy>) -> Self {
+        Self { strategy }
+    }
+}
+
+impl Strategy for Invert {
+    fn next(&mut self, candle: &Candle) -> Signal {
+        match self.strategy.next(candle) {
+            Signal::Buy => Signal::Sell,
+            Signal::Sell => Signal::Buy,
+            Signal::Hold => Signal::Hold,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.strategy.reset();
+    }
+
+    fn box_clone(&self) -> Box<dyn Strategy> {
+        Box::new(self.clone())
+    }
+
+    fn warm_up(&self) -> usize {
+        self.strategy.warm_up()
+    }
+
+    fn diagnostics(&self) -> Vec<(String, f64)> {
+        self.strategy.diagnostics()
+    }
+}
+
+/// After passing a `Buy`/`Sell` through, suppresses further signals for
+/// `bars` candles (debounces strategies that flip back and forth).
+#[derive(Clone)]
+pub struct Cooldown {
+    pub strategy: Box<dyn Strategy>,
+    pub bars: usize,
+    remaining: usize,
+}
+
+impl Cooldown {
+    pub fn new(strategy: Box<dyn Strategy>, bars: usize) -> Self {
+        Self { strategy, bars, remaining: 0 }
+    }
+}
+
+impl Strategy for Cooldown {
+    fn next(&mut self, candle: &Candle) -> Signal {
+        let signal = self.strategy.next(candle);
+        if self.remaining > 0 {
+            self.remaining -= 1;
+            return Signal::Hold;
+        }
+        if signal != Signal::Hold {
+            self.remaining = self.bars;
+        }
+        signal
+    }
+
+    fn reset(&mut self) {
+        self.strategy.reset();
+        self.remaining = 0;
+    }
+
+    fn box_clone(&self) -> Box<dyn Strategy> {
+        Box::new(self.clone())
+    }
+
+    fn warm_up(&self) -> usize {
+        self.strategy.warm_up()
+    }
+
+    fn diagnostics(&self) -> Vec<(String, f64)> {
+        let mut values = vec![("cooldown_remaining".to_string(), self.remaining as f64)];
+        values.extend(self.strategy.diagnostics());
+        values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Replays a fixed list of signals, then holds.
+    #[derive(Clone)]
+    struct Scripted {
+        signals: Vec<Signal>,
+        pos: usize,
+    }
+
+    fn scripted(signals: &[Signal]) -> Box<dyn Strategy> {
+        Box::new(Scripted { signals: signals.to_vec(), pos: 0 })
+    }
+
+    impl Strategy for Scripted {
+        fn next(&mut self, _candle: &Candle) -> Signal {
+            let signal = self.signals.get(self.pos).copied().unwrap_or(Signal::Hold);
+            self.pos += 1;
+            signal
+        }
+
+        fn reset(&mut self) {
+            self.pos = 0;
+        }
+
+        fn box_clone(&self) -> Box<dyn Strategy> {
+            Box::new(self.clone())
+        }
+    }
+
+    fn run(strategy: &mut dyn Strategy, candles: usize) -> Vec<Signal> {
+        let candle = Candle { timestamp: 0, open: 1.0, high: 1.0, low: 1.0, close: 1.0, volume: 0.0, period: 1.0 };
+        (0..candles).map(|_| strategy.next(&candle)).collect()
+    }
+
+    use Signal::{Buy, Hold, Sell};
+
+    #[test]
+    fn test_all_of_and_any_of() {
+        let a = [Buy, Buy, Sell, Hold];
+        let b = [Buy, Sell, Sell, Buy];
+
+        let mut all = AllOf::new(vec![scripted(&a), scripted(&b)]);
+        assert_eq!(run(&mut all, 4), vec![Buy, Hold, Sell, Hold]);
+
+        let mut any = AnyOf::new(vec![scripted(&a), scripted(&b)]);
+        assert_eq!(run(&mut any, 4), vec![Buy, Hold, Sell, Buy]);
+    }
+
+    #[test]
+    fn test_majority_vote() {
+        let mut vote = MajorityVote::new(vec![
+            scripted(&[Buy, Sell, Buy]),
+            scripted(&[Buy, Sell, Hold]),
+            scripted(&[Sell, Hold, Hold]),
+        ]);
+        assert_eq!(run(&mut vote, 3), vec![Buy, Sell, Hold]);
+    }
+
+    #[test]
+    fn test_regime_filter() {
+        let mut filtered = RegimeFilter::new(
+            scripted(&[Buy, Buy, Sell, Buy, Buy]),
+            scripted(&[Hold, Buy, Hold, Sell, Hold]),
+        );
+        assert_eq!(run(&mut filtered, 5), vec![Hold, Buy, Sell, Hold, Hold]);
+    }
+
+    #[test]
+    fn test_invert_and_cooldown() {
+        let mut inverted = Invert::new(scripted(&[Buy, Hold, Sell]));
+        assert_eq!(run(&mut inverted, 3), vec![Sell, Hold, Buy]);
+
+        let mut cooled = Cooldown::new(scripted(&[Buy, Sell, Buy, Sell, Sell]), 2);
+        assert_eq!(run(&mut cooled, 5), vec![Buy, Hold, Hold, Sell, Hold]);
+
+        cooled.reset();
+        assert_eq!(run(&mut cooled, 1), vec![Buy]);
+    }
+
+    #[test]
+    fn test_warm_up_is_the_slowest_child() {
+        use crate::strategy::{RsiReversion, SmaCross};
+
+        let filtered = RegimeFilter::new(Box::new(RsiReversion::new(14, 30.0, 70.0)), Box::new(SmaCross::new(10, 50)));
+        assert_eq!(filtered.warm_up(), 50);
+        assert_eq!(Cooldown::new(Box::new(filtered), 3).warm_up(), 50);
+        assert_eq!(AnyOf::new(Vec::new()).warm_up(), 0);
+    }
+}
@@ -0,0 +1,158 @@
+use crate::types::Signal;
+
+/// How a crossover strategy turns the short/long relationship into signals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrossMode {
+    /// `Buy` on every candle where short > long, `Sell` where short < long.
+    Level,
+    /// `Buy`/`Sell` only on the candle where the cross is confirmed.
+    Edge,
+}
+
+impl CrossMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "level" => Some(CrossMode::Level),
+            "edge" => Some(CrossMode::Edge),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Above,
+    Below,
+}
+
+/// Tracks which side of the long line the short line is on and detects crosses.
+///
+/// * `hysteresis` is a band around the long value, as a fraction of it: the
+///   short line has to be above `long * (1 + hysteresis)` to count as above and
+///   below `long * (1 - hysteresis)` to count as below. Inside the band the
+///   confirmed side does not change.
+/// * `confirmation_bars` is how many extra candles the short line has to stay
+///   on the new side before the cross is accepted.
+///
+/// In `CrossMode::Level` the signal is `Buy`/`Sell` only while the short line
+/// is outside the band on the confirmed side; inside the band, or while a new
+/// side is still being confirmed, it is `Hold`.
+#[derive(Debug, Clone)]
+pub struct CrossDetector {
+    pub mode: CrossMode,
+    pub confirmation_bars: usize,
+    pub hysteresis: f64,
+    side: Option<Side>,
+    streak: Option<(Side, usize)>,
+    last_cross: Signal,
+}
+
+impl CrossDetector {
+    pub fn new(mode: CrossMode) -> Self {
+        Self {
+            mode,
+            confirmation_bars: 0,
+            hysteresis: 0.0,
+            side: None,
+            streak: None,
+            last_cross: Signal::Hold,
+        }
+    }
+
+    pub fn update(&mut self, short: f64, long: f64) -> Signal {
+        let band = long.abs() * self.hysteresis;
+        let raw = if short > long + band {
+            Some(Side::Above)
+        } else if short < long - band {
+            Some(Side::Below)
+        } else {
+            None
+        };
+
+        self.streak = match (raw, self.streak) {
+            (Some(side), Some((prev, count))) if side == prev => Some((side, count + 1)),
+            (Some(side), _) => Some((side, 1)),
+            (None, _) => None,
+        };
+
+        // Accept the new side once it has held for the confirmation period
+        self.last_cross = Signal::Hold;
+        if let Some((side, count)) = self.streak {
+            if count > self.confirmation_bars && self.side != Some(side) {
+                if self.side.is_some() {
+                    self.last_cross = match side {
+                        Side::Above => Signal::Buy,
+                        Side::Below => Signal::Sell,
+                    };
+                }
+                self.side = Some(side);
+            }
+        }
+
+        match self.mode {
+            CrossMode::Edge => self.last_cross,
+            CrossMode::Level => match (raw, self.side) {
+                (Some(now), Some(confirmed)) if now == confirmed => match confirmed {
+                    Side::Above => Signal::Buy,
+                    Side::Below => Signal::Sell,
+                },
+                _ => Signal::Hold,
+            },
+        }
+    }
+
+    /// +1 for a bullish cross on the latest update, -1 for a bearish one, 0 otherwise.
+    pub fn last_cross(&self) -> f64 {
+        match self.last_cross {
+            Signal::Buy => 1.0,
+            Signal::Sell => -1.0,
+            Signal::Hold => 0.0,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.side = None;
+        self.streak = None;
+        self.last_cross = Signal::Hold;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Signal::{Buy, Hold, Sell};
+
+    fn run(detector: &mut CrossDetector, shorts: &[f64]) -> Vec<Signal> {
+        shorts.iter().map(|&s| detector.update(s, 10.0)).collect()
+    }
+
+    #[test]
+    fn test_level_mode_matches_plain_comparison() {
+        let mut detector = CrossDetector::new(CrossMode::Level);
+        assert_eq!(run(&mut detector, &[9.0, 10.0, 11.0, 12.0, 9.0]), vec![Sell, Hold, Buy, Buy, Sell]);
+    }
+
+    #[test]
+    fn test_edge_mode_signals_only_on_cross() {
+        let mut detector = CrossDetector::new(CrossMode::Edge);
+        assert_eq!(run(&mut detector, &[11.0, 9.0, 8.0, 11.0, 12.0]), vec![Hold, Sell, Hold, Buy, Hold]);
+    }
+
+    #[test]
+    fn test_confirmation_bars_delay_the_cross() {
+        let mut detector = CrossDetector::new(CrossMode::Edge);
+        detector.confirmation_bars = 2;
+        // A one-candle poke above is ignored, a sustained move is accepted on its third candle
+        let signals = run(&mut detector, &[9.0, 9.0, 9.0, 11.0, 9.0, 11.0, 11.0, 11.0]);
+        assert_eq!(signals, vec![Hold, Hold, Hold, Hold, Hold, Hold, Hold, Buy]);
+    }
+
+    #[test]
+    fn test_hysteresis_band_filters_small_crosses() {
+        let mut detector = CrossDetector::new(CrossMode::Edge);
+        detector.hysteresis = 0.05;
+        let signals = run(&mut detector, &[9.0, 10.4, 9.8, 10.6, 10.2, 9.6, 9.4]);
+        assert_eq!(signals, vec![Hold, Hold, Hold, Buy, Hold, Hold, Sell]);
+        assert_eq!(detector.last_cross(), -1.0);
+    }
+}
@@ -0,0 +1,88 @@
+use indicator::Indicator;
+
+use crate::rolling::{RollingMax, RollingMin};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Channel {
+    pub upper: f64,
+    pub lower: f64,
+    pub middle: f64,
+}
+
+/// Donchian channel: highest high and lowest low of the last `period` updates.
+#[derive(Debug, Clone)]
+pub struct DonchianChannel {
+    period: usize,
+    highs: RollingMax,
+    lows: RollingMin,
+}
+
+impl DonchianChannel {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period: period.max(1),
+            highs: RollingMax::new(period),
+            lows: RollingMin::new(period),
+        }
+    }
+
+    /// Add a bar and return the channel including it. Breakout strategies
+    /// usually compare against `get()` *before* updating with the current bar.
+    pub fn update(&mut self, high: f64, low: f64) -> Option<Channel> {
+        self.highs.push(high);
+        self.lows.push(low);
+        self.get()
+    }
+
+    /// Channel over the last `period` bars, once that many have been seen.
+    pub fn get(&self) -> Option<Channel> {
+        if !self.highs.is_full() {
+            return None;
+        }
+        let upper = self.highs.get()?;
+        let lower = self.lows.get()?;
+        Some(Channel { upper, lower, middle: (upper + lower) / 2.0 })
+    }
+
+    pub fn reset(&mut self) {
+        self.highs.clear();
+        self.lows.clear();
+    }
+}
+
+impl Indicator for DonchianChannel {
+    type Input = (f64, f64);
+    type Output = Channel;
+
+    fn next(&mut self, input: (f64, f64)) -> Option<Channel> {
+        self.update(input.0, input.1)
+    }
+
+    fn value(&self) -> Option<Channel> {
+        self.get()
+    }
+
+    fn reset(&mut self) {
+        DonchianChannel::reset(self)
+    }
+
+    fn warm_up(&self) -> usize {
+        self.period
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_donchian_channel() {
+        let mut channel = DonchianChannel::new(3);
+        assert_eq!(channel.update(10.0, 8.0), None);
+        assert_eq!(channel.update(12.0, 9.0), None);
+        assert_eq!(channel.update(11.0, 7.0), Some(Channel { upper: 12.0, lower: 7.0, middle: 9.5 }));
+        // The first bar drops out of the window
+        assert_eq!(channel.update(9.0, 8.5), Some(Channel { upper: 12.0, lower: 7.0, middle: 9.5 }));
+        assert_eq!(channel.update(9.5, 8.0), Some(Channel { upper: 11.0, lower: 7.0, middle: 9.0 }));
+    }
+}
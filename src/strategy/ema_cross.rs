@@ -1,10 +1,14 @@
 use crate::types::{Candle, Signal};
 use ema::Ema;
+use indicator::Indicator;
 use super::Strategy;
+use super::crossover::{CrossDetector, CrossMode};
 
+#[derive(Clone)]
 pub struct EmaCross {
     pub short_ema: Ema,
     pub long_ema: Ema,
+    pub detector: CrossDetector,
 }
 
 impl EmaCross {
@@ -12,8 +16,27 @@ impl EmaCross {
         Self {
             short_ema: Ema::new(short_period),
             long_ema: Ema::new(long_period),
+            detector: CrossDetector::new(CrossMode::Level),
         }
     }
+
+    /// Switch between level signals (default) and edge-triggered crossover events.
+    pub fn with_mode(mut self, mode: CrossMode) -> Self {
+        self.detector.mode = mode;
+        self
+    }
+
+    /// Require the short line to stay on the new side for `bars` extra candles.
+    pub fn with_confirmation(mut self, bars: usize) -> Self {
+        self.detector.confirmation_bars = bars;
+        self
+    }
+
+    /// Ignore crosses inside a band of `fraction` (e.g. 0.002 = 0.2%) around the long line.
+    pub fn with_hysteresis(mut self, fraction: f64) -> Self {
+        self.detector.hysteresis = fraction;
+        self
+    }
 }
 
 impl Strategy for EmaCross {
@@ -21,20 +44,34 @@ impl Strategy for EmaCross {
         let _short = self.short_ema.next(candle.close);
         let _long = self.long_ema.next(candle.close);
         match (self.short_ema.get(), self.long_ema.get()) {
-            (Some(short), Some(long)) => {
-                if short > long {
-                    Signal::Buy
-                } else if short < long {
-                    Signal::Sell
-                } else {
-                    Signal::Hold
-                }
-            }
+            (Some(short), Some(long)) => self.detector.update(short, long),
             _ => Signal::Hold,
         }
     }
     
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+    fn reset(&mut self) {
+        self.short_ema.reset();
+        self.long_ema.reset();
+        self.detector.reset();
+    }
+
+    fn box_clone(&self) -> Box<dyn Strategy> {
+        Box::new(self.clone())
+    }
+
+    fn warm_up(&self) -> usize {
+        self.short_ema.warm_up().max(self.long_ema.warm_up())
+    }
+
+    fn diagnostics(&self) -> Vec<(String, f64)> {
+        let mut values = Vec::new();
+        if let Some(short) = self.short_ema.get() {
+            values.push(("short_ema".to_string(), short));
+        }
+        if let Some(long) = self.long_ema.get() {
+            values.push(("long_ema".to_string(), long));
+            values.push(("cross".to_string(), self.detector.last_cross()));
+        }
+        values
     }
 }
\ No newline at end of file
@@ -0,0 +1,82 @@
+use ema::Ema;
+use indicator::Indicator;
+
+use crate::types::Candle;
+use super::{Bands, ATR};
+
+/// Keltner channel: EMA of the close plus/minus `multiplier` times the ATR.
+#[derive(Clone)]
+pub struct KeltnerChannel {
+    ema: Ema,
+    atr: ATR,
+    multiplier: f64,
+    value: Option<Bands>,
+}
+
+impl KeltnerChannel {
+    pub fn new(ema_period: usize, atr_period: usize, multiplier: f64) -> Self {
+        Self {
+            ema: Ema::new(ema_period),
+            atr: ATR::new(atr_period),
+            multiplier,
+            value: None,
+        }
+    }
+
+    pub fn update(&mut self, candle: &Candle) -> Option<Bands> {
+        let middle = self.ema.next(candle.close);
+        let atr = self.atr.update(candle)?;
+        self.value = Some(Bands {
+            middle,
+            upper: middle + self.multiplier * atr,
+            lower: middle - self.multiplier * atr,
+        });
+        self.value
+    }
+
+    pub fn get(&self) -> Option<Bands> {
+        self.value
+    }
+
+    pub fn reset(&mut self) {
+        self.ema.reset();
+        self.atr.reset();
+        self.value = None;
+    }
+}
+
+impl Indicator for KeltnerChannel {
+    type Input = Candle;
+    type Output = Bands;
+
+    fn next(&mut self, input: Candle) -> Option<Bands> {
+        self.update(&input)
+    }
+
+    fn value(&self) -> Option<Bands> {
+        self.get()
+    }
+
+    fn reset(&mut self) {
+        KeltnerChannel::reset(self)
+    }
+
+    fn warm_up(&self) -> usize {
+        self.ema.warm_up().max(self.atr.warm_up())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keltner_channel() {
+        let mut keltner = KeltnerChannel::new(3, 2, 2.0);
+        let candle = |close: f64| Candle { timestamp: 0, open: close, high: close + 1.0, low: close - 1.0, close, volume: 1.0, period: 1.0 };
+        assert_eq!(keltner.update(&candle(10.0)), None);
+        let bands = keltner.update(&candle(10.0)).unwrap();
+        // Constant range of 2.0 => ATR 2.0
+        assert_eq!(bands, Bands { middle: 10.0, upper: 14.0, lower: 6.0 });
+    }
+}
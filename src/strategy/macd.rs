@@ -0,0 +1,104 @@
+use ema::Ema;
+use indicator::Indicator;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MacdValue {
+    pub macd: f64,
+    pub signal: f64,
+    pub histogram: f64,
+}
+
+/// Moving Average Convergence Divergence built on the `ema` crate.
+///
+/// The MACD line is reported once `slow_period` prices have been seen, and the
+/// signal line (an EMA of the MACD line) after a further `signal_period - 1`.
+#[derive(Debug, Clone)]
+pub struct Macd {
+    fast: Ema,
+    slow: Ema,
+    signal: Ema,
+    slow_period: usize,
+    signal_period: usize,
+    count: usize,
+    value: Option<MacdValue>,
+}
+
+impl Macd {
+    pub fn new(fast_period: usize, slow_period: usize, signal_period: usize) -> Self {
+        Self {
+            fast: Ema::new(fast_period),
+            slow: Ema::new(slow_period),
+            signal: Ema::new(signal_period),
+            slow_period: slow_period.max(1),
+            signal_period: signal_period.max(1),
+            count: 0,
+            value: None,
+        }
+    }
+
+    pub fn next(&mut self, price: f64) -> Option<MacdValue> {
+        let macd = self.fast.next(price) - self.slow.next(price);
+        self.count += 1;
+        if self.count < self.slow_period {
+            return None;
+        }
+
+        let signal = self.signal.next(macd);
+        if self.count < self.slow_period + self.signal_period - 1 {
+            return None;
+        }
+
+        self.value = Some(MacdValue { macd, signal, histogram: macd - signal });
+        self.value
+    }
+
+    pub fn get(&self) -> Option<MacdValue> {
+        self.value
+    }
+
+    pub fn reset(&mut self) {
+        self.fast.reset();
+        self.slow.reset();
+        self.signal.reset();
+        self.count = 0;
+        self.value = None;
+    }
+}
+
+impl Indicator for Macd {
+    type Input = f64;
+    type Output = MacdValue;
+
+    fn next(&mut self, input: f64) -> Option<MacdValue> {
+        Macd::next(self, input)
+    }
+
+    fn value(&self) -> Option<MacdValue> {
+        self.get()
+    }
+
+    fn reset(&mut self) {
+        Macd::reset(self)
+    }
+
+    fn warm_up(&self) -> usize {
+        self.slow_period + self.signal_period - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_macd_warm_up_and_trend() {
+        let mut macd = Macd::new(3, 6, 3);
+        let outputs: Vec<Option<MacdValue>> = (0..20).map(|i| macd.next(100.0 + i as f64)).collect();
+
+        assert!(outputs[..7].iter().all(Option::is_none));
+        let last = outputs[19].unwrap();
+        // In a steady uptrend the fast EMA leads the slow one
+        assert!(last.macd > 0.0);
+        assert!((last.histogram - (last.macd - last.signal)).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,54 @@
+use crate::types::{Candle, Signal};
+use indicator::Indicator;
+use super::crossover::{CrossDetector, CrossMode};
+use super::{Macd, Strategy};
+
+/// Buys when the MACD line crosses above its signal line and sells on the cross below.
+#[derive(Clone)]
+pub struct MacdCross {
+    pub macd: Macd,
+    pub detector: CrossDetector,
+}
+
+impl MacdCross {
+    pub fn new(fast_period: usize, slow_period: usize, signal_period: usize) -> Self {
+        Self {
+            macd: Macd::new(fast_period, slow_period, signal_period),
+            detector: CrossDetector::new(CrossMode::Edge),
+        }
+    }
+}
+
+impl Strategy for MacdCross {
+    fn next(&mut self, candle: &Candle) -> Signal {
+        match self.macd.next(candle.close) {
+            Some(value) => self.detector.update(value.macd, value.signal),
+            None => Signal::Hold,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.macd.reset();
+        self.detector.reset();
+    }
+
+    fn box_clone(&self) -> Box<dyn Strategy> {
+        Box::new(self.clone())
+    }
+
+    fn warm_up(&self) -> usize {
+        self.macd.warm_up()
+    }
+
+    fn diagnostics(&self) -> Vec<(String, f64)> {
+        match self.macd.get() {
+            Some(value) => vec![
+                ("macd".to_string(), value.macd),
+                ("signal".to_string(), value.signal),
+                ("histogram".to_string(), value.histogram),
+                ("cross".to_string(), self.detector.last_cross()),
+            ],
+            None => Vec::new(),
+        }
+    }
+}
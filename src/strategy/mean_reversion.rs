@@ -1,7 +1,9 @@
 use crate::types::{Candle, Signal};
 use return_quant::Returns;
+use indicator::Indicator;
 use super::Strategy;
 
+#[derive(Clone)]
 pub struct MeanReversion {
     pub returns: Returns,
     pub threshold: f64,
@@ -27,7 +29,22 @@ impl Strategy for MeanReversion {
         }
     }
     
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+    fn reset(&mut self) {
+        self.returns.reset();
+    }
+
+    fn box_clone(&self) -> Box<dyn Strategy> {
+        Box::new(self.clone())
+    }
+
+    fn warm_up(&self) -> usize {
+        self.returns.warm_up()
+    }
+
+    fn diagnostics(&self) -> Vec<(String, f64)> {
+        self.returns
+            .get()
+            .map(|r| vec![("return".to_string(), r)])
+            .unwrap_or_default()
     }
 }
\ No newline at end of file
@@ -0,0 +1,118 @@
+use std::collections::VecDeque;
+
+use indicator::Indicator;
+
+use crate::types::Candle;
+
+/// Money Flow Index: a volume-weighted RSI over `period` candles (0-100).
+#[derive(Debug, Clone)]
+pub struct Mfi {
+    period: usize,
+    prev_typical: Option<f64>,
+    flows: VecDeque<(f64, f64)>,
+    positive_sum: f64,
+    negative_sum: f64,
+    value: Option<f64>,
+}
+
+impl Mfi {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period: period.max(1),
+            prev_typical: None,
+            flows: VecDeque::with_capacity(period + 1),
+            positive_sum: 0.0,
+            negative_sum: 0.0,
+            value: None,
+        }
+    }
+
+    pub fn update(&mut self, candle: &Candle) -> Option<f64> {
+        let typical = (candle.high + candle.low + candle.close) / 3.0;
+        let prev = self.prev_typical.replace(typical)?;
+
+        let money_flow = typical * candle.volume;
+        let flow = if typical > prev {
+            (money_flow, 0.0)
+        } else if typical < prev {
+            (0.0, money_flow)
+        } else {
+            (0.0, 0.0)
+        };
+        self.positive_sum += flow.0;
+        self.negative_sum += flow.1;
+        self.flows.push_back(flow);
+        if self.flows.len() > self.period {
+            if let Some((pos, neg)) = self.flows.pop_front() {
+                self.positive_sum -= pos;
+                self.negative_sum -= neg;
+            }
+        }
+        if self.flows.len() < self.period {
+            return None;
+        }
+
+        let mfi = if self.negative_sum <= 0.0 {
+            if self.positive_sum <= 0.0 { 50.0 } else { 100.0 }
+        } else {
+            100.0 - 100.0 / (1.0 + self.positive_sum / self.negative_sum)
+        };
+        self.value = Some(mfi);
+        self.value
+    }
+
+    pub fn get(&self) -> Option<f64> {
+        self.value
+    }
+
+    pub fn reset(&mut self) {
+        self.prev_typical = None;
+        self.flows.clear();
+        self.positive_sum = 0.0;
+        self.negative_sum = 0.0;
+        self.value = None;
+    }
+}
+
+impl Indicator for Mfi {
+    type Input = Candle;
+    type Output = f64;
+
+    fn next(&mut self, input: Candle) -> Option<f64> {
+        self.update(&input)
+    }
+
+    fn value(&self) -> Option<f64> {
+        self.get()
+    }
+
+    fn reset(&mut self) {
+        Mfi::reset(self)
+    }
+
+    /// `period` money flows need one extra candle.
+    fn warm_up(&self) -> usize {
+        self.period + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(price: f64, volume: f64) -> Candle {
+        Candle { timestamp: 0, open: price, high: price, low: price, close: price, volume, period: 1.0 }
+    }
+
+    #[test]
+    fn test_mfi() {
+        let mut mfi = Mfi::new(2);
+        assert_eq!(mfi.update(&candle(10.0, 1.0)), None);
+        assert_eq!(mfi.update(&candle(11.0, 1.0)), None);
+        // positive flow 11, negative flow 10 * 2 = 20
+        let value = mfi.update(&candle(10.0, 2.0)).unwrap();
+        assert!((value - (100.0 - 100.0 / (1.0 + 11.0 / 20.0))).abs() < 1e-9);
+        // window drops the first flow: only negative flow left
+        assert_eq!(mfi.update(&candle(9.0, 1.0)), Some(0.0));
+    }
+}
@@ -0,0 +1,77 @@
+use indicator::Indicator;
+
+use crate::types::Candle;
+
+/// On-Balance Volume: running total of volume, added on up closes and
+/// subtracted on down closes.
+#[derive(Debug, Clone, Default)]
+pub struct Obv {
+    prev_close: Option<f64>,
+    value: f64,
+}
+
+impl Obv {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, candle: &Candle) -> f64 {
+        if let Some(prev) = self.prev_close {
+            if candle.close > prev {
+                self.value += candle.volume;
+            } else if candle.close < prev {
+                self.value -= candle.volume;
+            }
+        }
+        self.prev_close = Some(candle.close);
+        self.value
+    }
+
+    pub fn get(&self) -> f64 {
+        self.value
+    }
+
+    pub fn reset(&mut self) {
+        self.prev_close = None;
+        self.value = 0.0;
+    }
+}
+
+impl Indicator for Obv {
+    type Input = Candle;
+    type Output = f64;
+
+    fn next(&mut self, input: Candle) -> Option<f64> {
+        Some(self.update(&input))
+    }
+
+    fn value(&self) -> Option<f64> {
+        Some(self.get())
+    }
+
+    fn reset(&mut self) {
+        Obv::reset(self)
+    }
+
+    fn warm_up(&self) -> usize {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_obv() {
+        let mut obv = Obv::new();
+        let candles = [(10.0, 5.0), (11.0, 2.0), (10.5, 4.0), (10.5, 9.0), (12.0, 1.0)];
+        let values: Vec<f64> = candles
+            .iter()
+            .map(|&(close, volume)| {
+                obv.update(&Candle { timestamp: 0, open: close, high: close, low: close, close, volume, period: 1.0 })
+            })
+            .collect();
+        assert_eq!(values, vec![0.0, 2.0, -2.0, -2.0, -1.0]);
+    }
+}
@@ -0,0 +1,746 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use super::crossover::CrossMode;
+use super::AtrSmoothing;
+use super::{
+    ATRBreakout, BollingerReversion, Cooldown, EmaCross, Invert, MacdCross, MeanReversion, Momentum,
+    RegimeFilter, RsiReversion, SmaCross, Strategy, Turtle, VolatilityTargeting, VolumeBreakout,
+};
+
+/// A single parameter value passed to a strategy constructor.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParamValue {
+    Int(i64),
+    Float(f64),
+    Text(String),
+}
+
+impl fmt::Display for ParamValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamValue::Int(v) => write!(f, "{}", v),
+            ParamValue::Float(v) => write!(f, "{}", v),
+            ParamValue::Text(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParamKind {
+    Int,
+    Float,
+    Text,
+    /// Name of another registered strategy. Parameters for it are passed with
+    /// the `<param>.` prefix, e.g. `base.short_period`.
+    Strategy,
+}
+
+/// Raw parameters as supplied by a caller (config file, CLI, optimizer...).
+pub type Params = HashMap<String, ParamValue>;
+
+#[derive(Debug, Clone)]
+pub struct ParamSpec {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub kind: ParamKind,
+    pub default: ParamValue,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    /// Allowed values for text parameters.
+    pub choices: Option<&'static [&'static str]>,
+}
+
+impl ParamSpec {
+    pub fn int(name: &'static str, description: &'static str, default: i64, min: i64, max: i64) -> Self {
+        Self {
+            name,
+            description,
+            kind: ParamKind::Int,
+            default: ParamValue::Int(default),
+            min: Some(min as f64),
+            max: Some(max as f64),
+            choices: None,
+        }
+    }
+
+    pub fn float(name: &'static str, description: &'static str, default: f64, min: f64, max: f64) -> Self {
+        Self {
+            name,
+            description,
+            kind: ParamKind::Float,
+            default: ParamValue::Float(default),
+            min: Some(min),
+            max: Some(max),
+            choices: None,
+        }
+    }
+
+    pub fn choice(
+        name: &'static str,
+        description: &'static str,
+        default: &'static str,
+        choices: &'static [&'static str],
+    ) -> Self {
+        Self {
+            name,
+            description,
+            kind: ParamKind::Text,
+            default: ParamValue::Text(default.to_string()),
+            min: None,
+            max: None,
+            choices: Some(choices),
+        }
+    }
+
+    pub fn strategy(name: &'static str, description: &'static str, default: &str) -> Self {
+        Self {
+            name,
+            description,
+            kind: ParamKind::Strategy,
+            default: ParamValue::Text(default.to_string()),
+            min: None,
+            max: None,
+            choices: None,
+        }
+    }
+
+    /// Parse a textual value (e.g. from the command line) into this parameter's type.
+    pub fn parse(&self, raw: &str) -> Result<ParamValue, RegistryError> {
+        let raw = raw.trim();
+        let invalid = || RegistryError::InvalidValue {
+            param: self.name.to_string(),
+            value: raw.to_string(),
+            expected: self.kind,
+        };
+        match self.kind {
+            ParamKind::Int => raw.parse().map(ParamValue::Int).map_err(|_| invalid()),
+            ParamKind::Float => raw.parse().map(ParamValue::Float).map_err(|_| invalid()),
+            ParamKind::Text | ParamKind::Strategy => Ok(ParamValue::Text(raw.to_string())),
+        }
+    }
+
+    /// Check type and range of `value`, coercing integers to floats where needed.
+    fn validate(&self, value: &ParamValue) -> Result<ParamValue, RegistryError> {
+        let value = match (self.kind, value) {
+            (ParamKind::Int, ParamValue::Int(_)) => value.clone(),
+            (ParamKind::Float, ParamValue::Float(_)) => value.clone(),
+            (ParamKind::Float, ParamValue::Int(v)) => ParamValue::Float(*v as f64),
+            (ParamKind::Text | ParamKind::Strategy, ParamValue::Text(_)) => value.clone(),
+            _ => {
+                return Err(RegistryError::InvalidValue {
+                    param: self.name.to_string(),
+                    value: value.to_string(),
+                    expected: self.kind,
+                })
+            }
+        };
+
+        let numeric = match value {
+            ParamValue::Int(v) => Some(v as f64),
+            ParamValue::Float(v) => Some(v),
+            ParamValue::Text(_) => None,
+        };
+        if let (Some(choices), ParamValue::Text(text)) = (self.choices, &value) {
+            if !choices.contains(&text.as_str()) {
+                return Err(RegistryError::InvalidValue {
+                    param: self.name.to_string(),
+                    value: text.clone(),
+                    expected: self.kind,
+                });
+            }
+        }
+        if let Some(v) = numeric {
+            let below = self.min.is_some_and(|min| v < min);
+            let above = self.max.is_some_and(|max| v > max);
+            if below || above || v.is_nan() {
+                return Err(RegistryError::OutOfRange {
+                    param: self.name.to_string(),
+                    value: v,
+                    min: self.min,
+                    max: self.max,
+                });
+            }
+        }
+        Ok(value)
+    }
+}
+
+/// Validated parameters with defaults filled in, handed to a strategy builder.
+#[derive(Debug, Clone)]
+pub struct ParamSet {
+    values: Vec<(&'static str, ParamValue)>,
+    nested: HashMap<String, Params>,
+}
+
+impl ParamSet {
+    pub fn get(&self, name: &str) -> Option<&ParamValue> {
+        self.values.iter().find(|(n, _)| *n == name).map(|(_, v)| v)
+    }
+
+    pub fn int(&self, name: &str) -> i64 {
+        match self.get(name) {
+            Some(ParamValue::Int(v)) => *v,
+            _ => panic!("parameter `{}` is not an integer", name),
+        }
+    }
+
+    pub fn usize(&self, name: &str) -> usize {
+        self.int(name).max(0) as usize
+    }
+
+    pub fn float(&self, name: &str) -> f64 {
+        match self.get(name) {
+            Some(ParamValue::Float(v)) => *v,
+            Some(ParamValue::Int(v)) => *v as f64,
+            _ => panic!("parameter `{}` is not a number", name),
+        }
+    }
+
+    pub fn text(&self, name: &str) -> &str {
+        match self.get(name) {
+            Some(ParamValue::Text(v)) => v,
+            _ => panic!("parameter `{}` is not text", name),
+        }
+    }
+
+    /// Parameters forwarded to the nested strategy held in parameter `name`.
+    pub fn nested(&self, name: &str) -> Params {
+        self.nested.get(name).cloned().unwrap_or_default()
+    }
+}
+
+const CROSS_MODES: &[&str] = &["level", "edge"];
+const ATR_SMOOTHINGS: &[&str] = &["simple", "wilder", "exponential"];
+
+fn atr_smoothing(params: &ParamSet) -> AtrSmoothing {
+    AtrSmoothing::from_name(params.text("atr_smoothing")).unwrap_or(AtrSmoothing::Simple)
+}
+
+fn cross_mode(params: &ParamSet) -> CrossMode {
+    // `choices` validation guarantees a known name
+    CrossMode::from_name(params.text("mode")).unwrap_or(CrossMode::Level)
+}
+
+type BuildFn = fn(&ParamSet, &Registry) -> Result<Box<dyn Strategy>, RegistryError>;
+
+/// Metadata and constructor for one strategy type.
+pub struct StrategySpec {
+    /// Stable identifier used in configs and on the command line.
+    pub name: &'static str,
+    /// Human readable name used in output.
+    pub title: &'static str,
+    pub description: &'static str,
+    pub params: Vec<ParamSpec>,
+    pub build: BuildFn,
+}
+
+impl StrategySpec {
+    pub fn param(&self, name: &str) -> Option<&ParamSpec> {
+        self.params.iter().find(|p| p.name == name)
+    }
+
+    /// Validate `params` against the schema and fill in defaults.
+    pub fn resolve(&self, params: &Params) -> Result<ParamSet, RegistryError> {
+        let mut nested: HashMap<String, Params> = HashMap::new();
+        for (key, value) in params {
+            if self.param(key).is_some() {
+                continue;
+            }
+            let forwarded = key.split_once('.').and_then(|(prefix, rest)| {
+                self.param(prefix)
+                    .filter(|p| p.kind == ParamKind::Strategy)
+                    .map(|p| (p.name, rest))
+            });
+            match forwarded {
+                Some((prefix, rest)) => {
+                    nested
+                        .entry(prefix.to_string())
+                        .or_default()
+                        .insert(rest.to_string(), value.clone());
+                }
+                None => {
+                    return Err(RegistryError::UnknownParam {
+                        strategy: self.name.to_string(),
+                        param: key.clone(),
+                    })
+                }
+            }
+        }
+
+        let mut values = Vec::with_capacity(self.params.len());
+        for spec in &self.params {
+            let value = match params.get(spec.name) {
+                Some(v) => spec.validate(v)?,
+                None => spec.default.clone(),
+            };
+            values.push((spec.name, value));
+        }
+        Ok(ParamSet { values, nested })
+    }
+}
+
+#[derive(Debug)]
+pub enum RegistryError {
+    UnknownStrategy(String),
+    UnknownParam { strategy: String, param: String },
+    InvalidValue { param: String, value: String, expected: ParamKind },
+    OutOfRange { param: String, value: f64, min: Option<f64>, max: Option<f64> },
+    /// Two parameters that must be strictly increasing, e.g. short and long periods.
+    ParamOrder { strategy: String, lower: String, upper: String },
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistryError::UnknownStrategy(name) => write!(f, "unknown strategy `{}`", name),
+            RegistryError::UnknownParam { strategy, param } => {
+                write!(f, "strategy `{}` has no parameter `{}`", strategy, param)
+            }
+            RegistryError::InvalidValue { param, value, expected } => {
+                write!(f, "invalid value `{}` for `{}` (expected {:?})", value, param, expected)
+            }
+            RegistryError::OutOfRange { param, value, min, max } => {
+                write!(f, "value {} for `{}` is out of range", value, param)?;
+                match (min, max) {
+                    (Some(min), Some(max)) => write!(f, " [{}, {}]", min, max),
+                    (Some(min), None) => write!(f, " [{}, ..)", min),
+                    (None, Some(max)) => write!(f, " (.., {}]", max),
+                    (None, None) => Ok(()),
+                }
+            }
+            RegistryError::ParamOrder { strategy, lower, upper } => {
+                write!(f, "strategy `{}` needs `{}` below `{}`", strategy, lower, upper)
+            }
+        }
+    }
+}
+
+impl Error for RegistryError {}
+
+/// Reject parameter sets where `lower` is not below `upper`, which would
+/// build an inverted strategy.
+fn check_order(p: &ParamSet, strategy: &str, lower: &str, upper: &str) -> Result<(), RegistryError> {
+    ordered(p.usize(lower) < p.usize(upper), strategy, lower, upper)
+}
+
+/// `check_order` for float parameters.
+fn check_float_order(p: &ParamSet, strategy: &str, lower: &str, upper: &str) -> Result<(), RegistryError> {
+    ordered(p.float(lower) < p.float(upper), strategy, lower, upper)
+}
+
+fn ordered(in_order: bool, strategy: &str, lower: &str, upper: &str) -> Result<(), RegistryError> {
+    if in_order {
+        Ok(())
+    } else {
+        Err(RegistryError::ParamOrder {
+            strategy: strategy.to_string(),
+            lower: lower.to_string(),
+            upper: upper.to_string(),
+        })
+    }
+}
+
+/// Collection of strategy specs that can construct strategies by name.
+#[derive(Default)]
+pub struct Registry {
+    specs: Vec<StrategySpec>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self { specs: Vec::new() }
+    }
+
+    /// Registry containing every built-in strategy.
+    pub fn builtin() -> Self {
+        let mut registry = Self::new();
+
+        registry.register(StrategySpec {
+            name: "ema_cross",
+            title: "EMA Cross",
+            description: "Long while the short EMA is above the long EMA",
+            params: vec![
+                ParamSpec::int("short_period", "Short EMA period", 9, 1, 1000),
+                ParamSpec::int("long_period", "Long EMA period", 21, 1, 1000),
+                ParamSpec::choice("mode", "`level` signals every candle, `edge` only on crosses", "level", CROSS_MODES),
+                ParamSpec::int("confirmation_bars", "Extra candles a cross must hold", 0, 0, 1000),
+                ParamSpec::float("hysteresis", "Band around the long EMA as a fraction of it", 0.0, 0.0, 1.0),
+            ],
+            build: |p, _| {
+                check_order(p, "ema_cross", "short_period", "long_period")?;
+                Ok(Box::new(
+                    EmaCross::new(p.usize("short_period"), p.usize("long_period"))
+                        .with_mode(cross_mode(p))
+                        .with_confirmation(p.usize("confirmation_bars"))
+                        .with_hysteresis(p.float("hysteresis")),
+                ))
+            },
+        });
+
+        registry.register(StrategySpec {
+            name: "sma_cross",
+            title: "SMA Cross",
+            description: "Long while the short SMA is above the long SMA",
+            params: vec![
+                ParamSpec::int("short_period", "Short SMA window", 10, 1, 1000),
+                ParamSpec::int("long_period", "Long SMA window", 30, 1, 1000),
+                ParamSpec::choice("mode", "`level` signals every candle, `edge` only on crosses", "level", CROSS_MODES),
+                ParamSpec::int("confirmation_bars", "Extra candles a cross must hold", 0, 0, 1000),
+                ParamSpec::float("hysteresis", "Band around the long SMA as a fraction of it", 0.0, 0.0, 1.0),
+            ],
+            build: |p, _| {
+                check_order(p, "sma_cross", "short_period", "long_period")?;
+                Ok(Box::new(
+                    SmaCross::new(p.usize("short_period"), p.usize("long_period"))
+                        .with_mode(cross_mode(p))
+                        .with_confirmation(p.usize("confirmation_bars"))
+                        .with_hysteresis(p.float("hysteresis")),
+                ))
+            },
+        });
+
+        registry.register(StrategySpec {
+            name: "mean_reversion",
+            title: "Mean Reversion",
+            description: "Buy after a drop larger than the threshold, sell after a rise",
+            params: vec![ParamSpec::float("threshold", "Single-candle return threshold", 0.01, 0.0, 1.0)],
+            build: |p, _| Ok(Box::new(MeanReversion::new(p.float("threshold")))),
+        });
+
+        registry.register(StrategySpec {
+            name: "momentum",
+            title: "Momentum",
+            description: "Buy after a rise larger than the threshold, sell after a drop",
+            params: vec![ParamSpec::float("threshold", "Single-candle return threshold", 0.01, 0.0, 1.0)],
+            build: |p, _| Ok(Box::new(Momentum::new(p.float("threshold")))),
+        });
+
+        registry.register(StrategySpec {
+            name: "atr_breakout",
+            title: "ATR Breakout",
+            description: "Trade closes outside the recent range when ATR is high enough",
+            params: vec![
+                ParamSpec::int("lookback", "Candles in the breakout range", 10, 1, 1000),
+                ParamSpec::int("atr_period", "ATR period", 14, 1, 1000),
+                ParamSpec::float("atr_multiplier", "Minimum ATR as a fraction of price", 0.01, 0.0, 1.0),
+                ParamSpec::choice("atr_smoothing", "How true ranges are averaged", "simple", ATR_SMOOTHINGS),
+            ],
+            build: |p, _| {
+                Ok(Box::new(
+                    ATRBreakout::new(p.usize("lookback"), p.usize("atr_period"), p.float("atr_multiplier"))
+                        .with_atr_smoothing(atr_smoothing(p)),
+                ))
+            },
+        });
+
+        registry.register(StrategySpec {
+            name: "volume_breakout",
+            title: "Volume Breakout",
+            description: "ATR Breakout that only buys when volume spikes above its average",
+            params: vec![
+                ParamSpec::int("lookback", "Candles in the breakout range", 10, 1, 1000),
+                ParamSpec::int("atr_period", "ATR period", 14, 1, 1000),
+                ParamSpec::float("atr_multiplier", "Minimum ATR as a fraction of price", 0.01, 0.0, 1.0),
+                ParamSpec::int("volume_period", "Candles in the average volume", 20, 1, 1000),
+                ParamSpec::float("volume_multiplier", "Volume / average volume needed to confirm", 1.5, 0.0, 100.0),
+                ParamSpec::choice("atr_smoothing", "How true ranges are averaged", "simple", ATR_SMOOTHINGS),
+            ],
+            build: |p, _| {
+                Ok(Box::new(
+                    VolumeBreakout::new(
+                        p.usize("lookback"),
+                        p.usize("atr_period"),
+                        p.float("atr_multiplier"),
+                        p.usize("volume_period"),
+                        p.float("volume_multiplier"),
+                    )
+                    .with_atr_smoothing(atr_smoothing(p)),
+                ))
+            },
+        });
+
+        registry.register(StrategySpec {
+            name: "turtle",
+            title: "Turtle",
+            description: "Donchian breakout entries, shorter channel and ATR stop exits",
+            params: vec![
+                ParamSpec::int("entry_period", "Breakout channel length", 20, 1, 1000),
+                ParamSpec::int("exit_period", "Exit channel length", 10, 1, 1000),
+                ParamSpec::int("atr_period", "ATR (N) period", 20, 1, 1000),
+                ParamSpec::float("risk_per_trade", "Equity fraction risked down to the ATR stop", 0.01, 0.0, 1.0),
+                ParamSpec::float("stop_atr", "Stop distance in ATRs", 2.0, 0.0, 100.0),
+                ParamSpec::choice("atr_smoothing", "How true ranges are averaged", "wilder", ATR_SMOOTHINGS),
+            ],
+            build: |p, _| {
+                Ok(Box::new(
+                    Turtle::new(
+                        p.usize("entry_period"),
+                        p.usize("exit_period"),
+                        p.usize("atr_period"),
+                        p.float("risk_per_trade"),
+                        p.float("stop_atr"),
+                    )
+                    .with_atr_smoothing(atr_smoothing(p)),
+                ))
+            },
+        });
+
+        registry.register(StrategySpec {
+            name: "volatility_targeting",
+            title: "Volatility Targeting",
+            description: "Scales exposure of a base strategy to hit a target volatility",
+            params: vec![
+                ParamSpec::float("target_volatility", "Target per-candle volatility", 0.01, 0.0, 1.0),
+                ParamSpec::int("lookback", "Candles used for realized volatility", 20, 2, 1000),
+                ParamSpec::strategy("base", "Strategy providing the signals", "ema_cross"),
+                ParamSpec::float("min_multiplier", "Lower bound for the position multiplier", 0.1, 0.0, 100.0),
+                ParamSpec::float("max_multiplier", "Upper bound for the position multiplier", 3.0, 0.0, 100.0),
+            ],
+            build: |p, registry| {
+                check_float_order(p, "volatility_targeting", "min_multiplier", "max_multiplier")?;
+                let base = registry.create(p.text("base"), &p.nested("base"))?;
+                Ok(Box::new(VolatilityTargeting::new(
+                    p.float("target_volatility"),
+                    p.usize("lookback"),
+                    base,
+                    p.float("min_multiplier"),
+                    p.float("max_multiplier"),
+                )))
+            },
+        });
+
+        registry.register(StrategySpec {
+            name: "rsi_reversion",
+            title: "RSI Reversion",
+            description: "Buy when RSI is oversold, sell when it is overbought",
+            params: vec![
+                ParamSpec::int("period", "RSI period", 14, 2, 1000),
+                ParamSpec::float("oversold", "Buy below this RSI", 30.0, 0.0, 100.0),
+                ParamSpec::float("overbought", "Sell above this RSI", 70.0, 0.0, 100.0),
+            ],
+            build: |p, _| {
+                check_float_order(p, "rsi_reversion", "oversold", "overbought")?;
+                Ok(Box::new(RsiReversion::new(p.usize("period"), p.float("oversold"), p.float("overbought"))))
+            },
+        });
+
+        registry.register(StrategySpec {
+            name: "macd_cross",
+            title: "MACD Cross",
+            description: "Trade crosses of the MACD line over its signal line",
+            params: vec![
+                ParamSpec::int("fast_period", "Fast EMA period", 12, 1, 1000),
+                ParamSpec::int("slow_period", "Slow EMA period", 26, 1, 1000),
+                ParamSpec::int("signal_period", "Signal line EMA period", 9, 1, 1000),
+            ],
+            build: |p, _| {
+                check_order(p, "macd_cross", "fast_period", "slow_period")?;
+                Ok(Box::new(MacdCross::new(
+                    p.usize("fast_period"),
+                    p.usize("slow_period"),
+                    p.usize("signal_period"),
+                )))
+            },
+        });
+
+        registry.register(StrategySpec {
+            name: "bollinger_reversion",
+            title: "Bollinger Reversion",
+            description: "Buy below the lower band, sell above the upper band",
+            params: vec![
+                ParamSpec::int("period", "SMA window", 20, 2, 1000),
+                ParamSpec::float("num_std", "Band width in standard deviations", 2.0, 0.0, 10.0),
+            ],
+            build: |p, _| Ok(Box::new(BollingerReversion::new(p.usize("period"), p.float("num_std")))),
+        });
+
+        registry.register(StrategySpec {
+            name: "regime_filter",
+            title: "Regime Filter",
+            description: "Only takes entries from one strategy while a filter strategy is bullish",
+            params: vec![
+                ParamSpec::strategy("signal", "Strategy providing entries and exits", "ema_cross"),
+                ParamSpec::strategy("filter", "Strategy defining the regime", "atr_breakout"),
+            ],
+            build: |p, registry| {
+                Ok(Box::new(RegimeFilter::new(
+                    registry.create(p.text("signal"), &p.nested("signal"))?,
+                    registry.create(p.text("filter"), &p.nested("filter"))?,
+                )))
+            },
+        });
+
+        registry.register(StrategySpec {
+            name: "invert",
+            title: "Invert",
+            description: "Swaps the buy and sell signals of another strategy",
+            params: vec![ParamSpec::strategy("base", "Strategy to invert", "momentum")],
+            build: |p, registry| Ok(Box::new(Invert::new(registry.create(p.text("base"), &p.nested("base"))?))),
+        });
+
+        registry.register(StrategySpec {
+            name: "cooldown",
+            title: "Cooldown",
+            description: "Ignores signals for a number of candles after each trade signal",
+            params: vec![
+                ParamSpec::strategy("base", "Strategy to debounce", "mean_reversion"),
+                ParamSpec::int("bars", "Candles to wait after a signal", 5, 0, 10_000),
+            ],
+            build: |p, registry| {
+                Ok(Box::new(Cooldown::new(
+                    registry.create(p.text("base"), &p.nested("base"))?,
+                    p.usize("bars"),
+                )))
+            },
+        });
+
+        registry
+    }
+
+    /// Add a spec, replacing any existing spec with the same name.
+    pub fn register(&mut self, spec: StrategySpec) {
+        self.specs.retain(|s| s.name != spec.name);
+        self.specs.push(spec);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&StrategySpec> {
+        self.specs.iter().find(|s| s.name == name)
+    }
+
+    pub fn specs(&self) -> &[StrategySpec] {
+        &self.specs
+    }
+
+    /// Construct strategy `name` from `params`; missing parameters use their defaults.
+    pub fn create(&self, name: &str, params: &Params) -> Result<Box<dyn Strategy>, RegistryError> {
+        let spec = self
+            .get(name)
+            .ok_or_else(|| RegistryError::UnknownStrategy(name.to_string()))?;
+        let resolved = spec.resolve(params)?;
+        (spec.build)(&resolved, self)
+    }
+
+    /// Short label such as `EMA Cross (9, 21)` for output tables.
+    pub fn label(&self, name: &str, params: &Params) -> Result<String, RegistryError> {
+        let spec = self
+            .get(name)
+            .ok_or_else(|| RegistryError::UnknownStrategy(name.to_string()))?;
+        let resolved = spec.resolve(params)?;
+
+        let mut values = Vec::new();
+        let mut nested = Vec::new();
+        for param in &spec.params {
+            match param.kind {
+                ParamKind::Strategy => {
+                    nested.push(self.label(resolved.text(param.name), &resolved.nested(param.name))?)
+                }
+                _ => values.push(resolved.get(param.name).map(|v| v.to_string()).unwrap_or_default()),
+            }
+        }
+
+        let mut label = format!("{} ({})", spec.title, values.join(", "));
+        for inner in nested {
+            label.push_str(" + ");
+            label.push_str(&inner);
+        }
+        Ok(label)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(pairs: &[(&str, ParamValue)]) -> Params {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.clone())).collect()
+    }
+
+    #[test]
+    fn test_builtin_strategies_build_with_defaults() {
+        let registry = Registry::builtin();
+        for spec in registry.specs() {
+            assert!(registry.create(spec.name, &Params::new()).is_ok(), "{}", spec.name);
+        }
+    }
+
+    #[test]
+    fn test_label_uses_resolved_params() {
+        let registry = Registry::builtin();
+        let label = registry
+            .label("ema_cross", &params(&[("short_period", ParamValue::Int(5))]))
+            .unwrap();
+        assert_eq!(label, "EMA Cross (5, 21, level, 0, 0)");
+
+        let nested = registry
+            .label("volatility_targeting", &params(&[("base.long_period", ParamValue::Int(50))]))
+            .unwrap();
+        assert_eq!(nested, "Volatility Targeting (0.01, 20, 0.1, 3) + EMA Cross (9, 50, level, 0, 0)");
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        let registry = Registry::builtin();
+        assert!(matches!(
+            registry.create("nope", &Params::new()),
+            Err(RegistryError::UnknownStrategy(_))
+        ));
+        assert!(matches!(
+            registry.create("ema_cross", &params(&[("period", ParamValue::Int(5))])),
+            Err(RegistryError::UnknownParam { .. })
+        ));
+        assert!(matches!(
+            registry.create("ema_cross", &params(&[("short_period", ParamValue::Int(0))])),
+            Err(RegistryError::OutOfRange { .. })
+        ));
+        assert!(matches!(
+            registry.create("ema_cross", &params(&[("short_period", ParamValue::Float(5.5))])),
+            Err(RegistryError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            registry.create("sma_cross", &params(&[("mode", ParamValue::Text("sideways".to_string()))])),
+            Err(RegistryError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            registry.create("volatility_targeting", &params(&[("base.bogus", ParamValue::Int(1))])),
+            Err(RegistryError::UnknownParam { .. })
+        ));
+        assert!(matches!(
+            registry.create("ema_cross", &params(&[("short_period", ParamValue::Int(21))])),
+            Err(RegistryError::ParamOrder { .. })
+        ));
+        assert!(matches!(
+            registry.create("sma_cross", &params(&[("short_period", ParamValue::Int(40))])),
+            Err(RegistryError::ParamOrder { .. })
+        ));
+        assert!(matches!(
+            registry.create("macd_cross", &params(&[("fast_period", ParamValue::Int(30))])),
+            Err(RegistryError::ParamOrder { .. })
+        ));
+        assert!(matches!(
+            registry.create("volatility_targeting", &params(&[("base.long_period", ParamValue::Int(5))])),
+            Err(RegistryError::ParamOrder { .. })
+        ));
+        assert!(matches!(
+            registry.create("volatility_targeting", &params(&[("min_multiplier", ParamValue::Float(4.0))])),
+            Err(RegistryError::ParamOrder { .. })
+        ));
+        assert!(matches!(
+            registry.create("rsi_reversion", &params(&[("oversold", ParamValue::Float(80.0)), ("overbought", ParamValue::Float(20.0))])),
+            Err(RegistryError::ParamOrder { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_and_coerce() {
+        let registry = Registry::builtin();
+        let spec = registry.get("momentum").unwrap();
+        let threshold = spec.param("threshold").unwrap();
+        assert_eq!(threshold.parse("0.02").unwrap(), ParamValue::Float(0.02));
+        assert!(threshold.parse("abc").is_err());
+
+        let resolved = spec.resolve(&params(&[("threshold", ParamValue::Int(0))])).unwrap();
+        assert_eq!(resolved.float("threshold"), 0.0);
+    }
+}
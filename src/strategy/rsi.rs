@@ -0,0 +1,120 @@
+use indicator::Indicator;
+
+/// Relative Strength Index using Wilder's smoothing.
+#[derive(Debug, Clone)]
+pub struct Rsi {
+    period: usize,
+    prev_close: Option<f64>,
+    avg_gain: f64,
+    avg_loss: f64,
+    count: usize,
+    value: Option<f64>,
+}
+
+impl Rsi {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period: period.max(1),
+            prev_close: None,
+            avg_gain: 0.0,
+            avg_loss: 0.0,
+            count: 0,
+            value: None,
+        }
+    }
+
+    /// Feed a close price; returns the RSI (0-100) once `period` price changes are known.
+    pub fn next(&mut self, price: f64) -> Option<f64> {
+        let prev = self.prev_close.replace(price)?;
+        let change = price - prev;
+        let gain = change.max(0.0);
+        let loss = (-change).max(0.0);
+        let n = self.period as f64;
+
+        self.count += 1;
+        if self.count <= self.period {
+            // Seed with a simple average of the first `period` changes
+            self.avg_gain += gain / n;
+            self.avg_loss += loss / n;
+            if self.count < self.period {
+                return None;
+            }
+        } else {
+            self.avg_gain = (self.avg_gain * (n - 1.0) + gain) / n;
+            self.avg_loss = (self.avg_loss * (n - 1.0) + loss) / n;
+        }
+
+        let rsi = if self.avg_loss == 0.0 {
+            if self.avg_gain == 0.0 { 50.0 } else { 100.0 }
+        } else {
+            100.0 - 100.0 / (1.0 + self.avg_gain / self.avg_loss)
+        };
+        self.value = Some(rsi);
+        self.value
+    }
+
+    pub fn get(&self) -> Option<f64> {
+        self.value
+    }
+
+    pub fn reset(&mut self) {
+        self.prev_close = None;
+        self.avg_gain = 0.0;
+        self.avg_loss = 0.0;
+        self.count = 0;
+        self.value = None;
+    }
+}
+
+impl Indicator for Rsi {
+    type Input = f64;
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Option<f64> {
+        Rsi::next(self, input)
+    }
+
+    fn value(&self) -> Option<f64> {
+        self.get()
+    }
+
+    fn reset(&mut self) {
+        Rsi::reset(self)
+    }
+
+    /// `period` price changes need one extra price.
+    fn warm_up(&self) -> usize {
+        self.period + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rsi_warm_up_and_extremes() {
+        let mut rsi = Rsi::new(3);
+        assert_eq!(rsi.next(10.0), None);
+        assert_eq!(rsi.next(11.0), None);
+        assert_eq!(rsi.next(12.0), None);
+        assert_eq!(rsi.next(13.0), Some(100.0));
+
+        let mut flat = Rsi::new(2);
+        flat.next(5.0);
+        flat.next(5.0);
+        assert_eq!(flat.next(5.0), Some(50.0));
+    }
+
+    #[test]
+    fn test_rsi_wilder_smoothing() {
+        let mut rsi = Rsi::new(2);
+        rsi.next(10.0);
+        rsi.next(12.0); // +2
+        let seeded = rsi.next(11.0).unwrap(); // -1 -> avg gain 1, avg loss 0.5
+        assert!((seeded - 100.0 * 2.0 / 3.0).abs() < 1e-9);
+
+        let smoothed = rsi.next(13.0).unwrap(); // +2 -> gain 1.5, loss 0.25
+        assert!((smoothed - (100.0 - 100.0 / 7.0)).abs() < 1e-9);
+    }
+}
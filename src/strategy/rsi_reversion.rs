@@ -0,0 +1,50 @@
+use crate::types::{Candle, Signal};
+use indicator::Indicator;
+use super::{Rsi, Strategy};
+
+/// Buys when RSI drops below `oversold` and sells when it rises above `overbought`.
+#[derive(Clone)]
+pub struct RsiReversion {
+    pub rsi: Rsi,
+    pub oversold: f64,
+    pub overbought: f64,
+}
+
+impl RsiReversion {
+    pub fn new(period: usize, oversold: f64, overbought: f64) -> Self {
+        Self {
+            rsi: Rsi::new(period),
+            oversold,
+            overbought,
+        }
+    }
+}
+
+impl Strategy for RsiReversion {
+    fn next(&mut self, candle: &Candle) -> Signal {
+        match self.rsi.next(candle.close) {
+            Some(rsi) if rsi < self.oversold => Signal::Buy,
+            Some(rsi) if rsi > self.overbought => Signal::Sell,
+            _ => Signal::Hold,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.rsi.reset();
+    }
+
+    fn box_clone(&self) -> Box<dyn Strategy> {
+        Box::new(self.clone())
+    }
+
+    fn warm_up(&self) -> usize {
+        self.rsi.warm_up()
+    }
+
+    fn diagnostics(&self) -> Vec<(String, f64)> {
+        self.rsi
+            .get()
+            .map(|rsi| vec![("rsi".to_string(), rsi)])
+            .unwrap_or_default()
+    }
+}
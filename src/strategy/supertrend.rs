@@ -0,0 +1,122 @@
+use indicator::Indicator;
+
+use crate::types::Candle;
+use super::ATR;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SupertrendValue {
+    /// Trailing stop line: below price in an uptrend, above it in a downtrend.
+    pub line: f64,
+    pub is_uptrend: bool,
+}
+
+/// Supertrend: ATR bands around the candle midpoint that only ratchet in the
+/// direction of the trend; the trend flips when the close crosses the band.
+#[derive(Clone)]
+pub struct Supertrend {
+    atr: ATR,
+    multiplier: f64,
+    upper: f64,
+    lower: f64,
+    prev_close: Option<f64>,
+    value: Option<SupertrendValue>,
+}
+
+impl Supertrend {
+    pub fn new(atr_period: usize, multiplier: f64) -> Self {
+        Self {
+            atr: ATR::new(atr_period),
+            multiplier,
+            upper: f64::MAX,
+            lower: f64::MIN,
+            prev_close: None,
+            value: None,
+        }
+    }
+
+    pub fn update(&mut self, candle: &Candle) -> Option<SupertrendValue> {
+        let prev_close = self.prev_close.replace(candle.close);
+        let atr = self.atr.update(candle)?;
+
+        let mid = (candle.high + candle.low) / 2.0;
+        let basic_upper = mid + self.multiplier * atr;
+        let basic_lower = mid - self.multiplier * atr;
+        let prev_close = prev_close.unwrap_or(candle.close);
+
+        // Bands only move towards price unless the previous close broke through them
+        if basic_upper < self.upper || prev_close > self.upper {
+            self.upper = basic_upper;
+        }
+        if basic_lower > self.lower || prev_close < self.lower {
+            self.lower = basic_lower;
+        }
+
+        let is_uptrend = match self.value {
+            Some(v) if v.is_uptrend => candle.close >= self.lower,
+            Some(_) => candle.close > self.upper,
+            None => candle.close >= mid,
+        };
+        let line = if is_uptrend { self.lower } else { self.upper };
+        self.value = Some(SupertrendValue { line, is_uptrend });
+        self.value
+    }
+
+    pub fn get(&self) -> Option<SupertrendValue> {
+        self.value
+    }
+
+    pub fn reset(&mut self) {
+        self.atr.reset();
+        self.upper = f64::MAX;
+        self.lower = f64::MIN;
+        self.prev_close = None;
+        self.value = None;
+    }
+}
+
+impl Indicator for Supertrend {
+    type Input = Candle;
+    type Output = SupertrendValue;
+
+    fn next(&mut self, input: Candle) -> Option<SupertrendValue> {
+        self.update(&input)
+    }
+
+    fn value(&self) -> Option<SupertrendValue> {
+        self.get()
+    }
+
+    fn reset(&mut self) {
+        Supertrend::reset(self)
+    }
+
+    fn warm_up(&self) -> usize {
+        self.atr.warm_up()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supertrend_flips_with_trend() {
+        let candle = |close: f64| Candle { timestamp: 0, open: close, high: close + 0.5, low: close - 0.5, close, volume: 1.0, period: 1.0 };
+        let mut supertrend = Supertrend::new(3, 2.0);
+
+        let mut last = None;
+        for i in 0..20 {
+            last = supertrend.update(&candle(100.0 + i as f64));
+        }
+        let up = last.unwrap();
+        assert!(up.is_uptrend);
+        assert!(up.line < 119.0);
+
+        for i in 0..10 {
+            last = supertrend.update(&candle(115.0 - 3.0 * i as f64));
+        }
+        let down = last.unwrap();
+        assert!(!down.is_uptrend);
+        assert!(down.line > 88.0);
+    }
+}
@@ -0,0 +1,219 @@
+use crate::sizing::{AtrRisk, PositionSizer};
+use crate::types::{Candle, Signal};
+use indicator::Indicator;
+use super::{AtrSmoothing, DonchianChannel, Strategy, ATR};
+
+/// Classic Turtle breakout.
+///
+/// Buys when the close breaks above the highest high of the last
+/// `entry_period` candles, and exits on a close below the lowest low of the
+/// last `exit_period` candles or when price falls `stop_atr` ATRs ("N") below
+/// the entry.
+///
+/// N is the Wilder-smoothed ATR, as in the original rules.
+///
+/// Position size follows the Turtle unit: risk `risk_per_trade` of equity
+/// between the entry and the stop. `sizer()` hands the matching `AtrRisk` to
+/// the trade model; `position_fraction()` reports the share of equity it
+/// commits.
+#[derive(Clone)]
+pub struct Turtle {
+    entry_channel: DonchianChannel,
+    exit_channel: DonchianChannel,
+    atr: ATR,
+    atr_period: usize,
+    atr_smoothing: AtrSmoothing,
+    pub risk_per_trade: f64,
+    pub stop_atr: f64,
+    last_atr: Option<f64>,
+    last_close: f64,
+    entry: Option<(f64, f64)>,
+}
+
+impl Turtle {
+    pub fn new(entry_period: usize, exit_period: usize, atr_period: usize, risk_per_trade: f64, stop_atr: f64) -> Self {
+        Self {
+            entry_channel: DonchianChannel::new(entry_period),
+            exit_channel: DonchianChannel::new(exit_period),
+            atr: ATR::new(atr_period).with_smoothing(AtrSmoothing::Wilder),
+            atr_period,
+            atr_smoothing: AtrSmoothing::Wilder,
+            risk_per_trade,
+            stop_atr,
+            last_atr: None,
+            last_close: 0.0,
+            entry: None,
+        }
+    }
+
+    pub fn with_atr_smoothing(mut self, smoothing: AtrSmoothing) -> Self {
+        self.atr = self.atr.with_smoothing(smoothing);
+        self.atr_smoothing = smoothing;
+        self
+    }
+
+    /// Fraction of equity to commit so that a move to the stop (`stop_atr`
+    /// ATRs) costs `risk_per_trade` of equity, capped at 1.0 (no leverage).
+    pub fn position_fraction(&self) -> Option<f64> {
+        let atr = self.last_atr?;
+        let stop_distance = atr * self.stop_atr;
+        if stop_distance <= 0.0 {
+            return Some(1.0);
+        }
+        Some((self.risk_per_trade * self.last_close / stop_distance).min(1.0))
+    }
+
+    /// Stop level of the open position, if any.
+    pub fn stop_price(&self) -> Option<f64> {
+        self.entry.map(|(price, n)| price - self.stop_atr * n)
+    }
+}
+
+impl Strategy for Turtle {
+    fn next(&mut self, candle: &Candle) -> Signal {
+        let atr = self.atr.update(candle);
+        self.last_atr = atr;
+        self.last_close = candle.close;
+
+        let entry_range = self.entry_channel.get();
+        let exit_range = self.exit_channel.get();
+        self.entry_channel.update(candle.high, candle.low);
+        self.exit_channel.update(candle.high, candle.low);
+
+        let (atr, entry_range) = match (atr, entry_range) {
+            (Some(atr), Some(range)) => (atr, range),
+            _ => return Signal::Hold,
+        };
+
+        match self.entry {
+            Some(_) => {
+                let stopped = self.stop_price().is_some_and(|stop| candle.close < stop);
+                let channel_exit = exit_range.is_some_and(|range| candle.close < range.lower);
+                if stopped || channel_exit {
+                    self.entry = None;
+                    Signal::Sell
+                } else {
+                    Signal::Hold
+                }
+            }
+            None if candle.close > entry_range.upper => {
+                self.entry = Some((candle.close, atr));
+                Signal::Buy
+            }
+            None => Signal::Hold,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.entry_channel.reset();
+        self.exit_channel.reset();
+        self.atr.reset();
+        self.last_atr = None;
+        self.last_close = 0.0;
+        self.entry = None;
+    }
+
+    fn box_clone(&self) -> Box<dyn Strategy> {
+        Box::new(self.clone())
+    }
+
+    fn warm_up(&self) -> usize {
+        self.atr.warm_up().max(self.entry_channel.warm_up() + 1)
+    }
+
+    fn sizer(&self) -> Option<Box<dyn PositionSizer>> {
+        let sizer = AtrRisk::new(self.risk_per_trade, self.atr_period, self.stop_atr)
+            .with_smoothing(self.atr_smoothing)
+            .with_max_fraction(1.0);
+        Some(Box::new(sizer))
+    }
+
+    fn diagnostics(&self) -> Vec<(String, f64)> {
+        let mut values = Vec::new();
+        if let Some(atr) = self.last_atr {
+            values.push(("atr".to_string(), atr));
+        }
+        if let Some(range) = self.entry_channel.get() {
+            values.push(("entry_high".to_string(), range.upper));
+        }
+        if let Some(range) = self.exit_channel.get() {
+            values.push(("exit_low".to_string(), range.lower));
+        }
+        if let Some(stop) = self.stop_price() {
+            values.push(("stop".to_string(), stop));
+        }
+        if let Some(fraction) = self.position_fraction() {
+            values.push(("position_fraction".to_string(), fraction));
+        }
+        values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest::backtest;
+    use crate::trade_model::TradeModel;
+
+    fn candle(close: f64) -> Candle {
+        Candle { timestamp: 0, open: close, high: close + 0.5, low: close - 0.5, close, volume: 1.0, period: 1.0 }
+    }
+
+    #[test]
+    fn test_turtle_entry_and_channel_exit() {
+        let mut turtle = Turtle::new(5, 3, 3, 0.01, 10.0);
+        let closes = [100.0, 100.0, 100.0, 100.0, 100.0, 102.0, 103.0, 101.0, 99.0, 98.0];
+        let signals: Vec<Signal> = closes.iter().map(|&c| turtle.next(&candle(c))).collect();
+
+        assert_eq!(signals[5], Signal::Buy);
+        assert!(signals[6..8].iter().all(|&s| s == Signal::Hold));
+        // 99.0 is below the lowest low (100.5) of the previous 3 candles
+        assert_eq!(signals[8], Signal::Sell);
+        assert_eq!(signals[9], Signal::Hold);
+    }
+
+    #[test]
+    fn test_turtle_atr_stop_and_sizing() {
+        let mut turtle = Turtle::new(3, 50, 3, 0.01, 2.0);
+        for c in [100.0, 100.0, 100.0, 104.0] {
+            turtle.next(&candle(c));
+        }
+        let stop = turtle.stop_price().unwrap();
+        assert!(stop < 104.0);
+        assert!(turtle.position_fraction().unwrap() <= 1.0);
+
+        assert_eq!(turtle.next(&candle(stop - 0.1)), Signal::Sell);
+        assert_eq!(turtle.stop_price(), None);
+    }
+
+    /// Size of the breakout entry on 100, 100, 100, 104 closes with candle
+    /// ranges of `close ± spread`, checked against `position_fraction`.
+    fn entry_size(spread: f64, risk_per_trade: f64, mut model: TradeModel) -> f64 {
+        let candles: Vec<Candle> = [100.0, 100.0, 100.0, 104.0]
+            .iter()
+            .map(|&c| Candle { high: c + spread, low: c - spread, ..candle(c) })
+            .collect();
+        let mut turtle = Turtle::new(3, 50, 3, risk_per_trade, 2.0);
+        model.sizer = turtle.sizer();
+        let result = backtest(&candles, &mut turtle, &mut model);
+        let trade = result.trades.last().unwrap();
+        assert_eq!(trade.action, Signal::Buy);
+        let expected = turtle.position_fraction().unwrap() * 1000.0 / 104.0;
+        assert!((trade.size - expected).abs() < 1e-9);
+        trade.size
+    }
+
+    #[test]
+    fn test_turtle_entry_size_follows_atr() {
+        // Same closes, wider ranges: a larger N should buy fewer units
+        let model = || TradeModel::new(1000.0, 0.0, 0.0, 1.0);
+        assert!(entry_size(2.0, 0.01, model()) < entry_size(0.5, 0.01, model()));
+    }
+
+    #[test]
+    fn test_turtle_sizer_capped_like_position_fraction() {
+        // Leverage leaves room for more, but the unit stays at all of the equity
+        let size = entry_size(0.5, 0.5, TradeModel::new(1000.0, 0.0, 0.0, 1.0).with_leverage(3.0, 0.1));
+        assert!((size - 1000.0 / 104.0).abs() < 1e-9);
+    }
+}
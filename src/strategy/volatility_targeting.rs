@@ -2,8 +2,8 @@ use crate::types::{Candle, Signal};
 use return_quant::Returns;
 use sharp_ratio::std_deviation;
 use super::Strategy;
-use std::any::Any;
 
+#[derive(Clone)]
 pub struct VolatilityTargeting {
     pub target_volatility: f64,      // Target daily volatility (e.g., 0.01 for 1%)
     pub lookback_period: usize,      // Period for calculating realized volatility (e.g., 20 days)
@@ -99,8 +99,33 @@ impl Strategy for VolatilityTargeting {
         self.base_signal_strategy.next(candle)
     }
     
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+    fn reset(&mut self) {
+        self.base_signal_strategy.reset();
+        self.returns_calculator.reset();
+        self.returns_history.clear();
+        self.position_multiplier = 1.0;
+    }
+
+    fn box_clone(&self) -> Box<dyn Strategy> {
+        Box::new(self.clone())
+    }
+
+    fn warm_up(&self) -> usize {
+        self.base_signal_strategy.warm_up()
+    }
+
+    fn diagnostics(&self) -> Vec<(String, f64)> {
+        let mut values = vec![
+            ("position_multiplier".to_string(), self.position_multiplier),
+            ("realized_volatility".to_string(), self.calculate_realized_volatility()),
+        ];
+        values.extend(
+            self.base_signal_strategy
+                .diagnostics()
+                .into_iter()
+                .map(|(name, value)| (format!("base.{}", name), value)),
+        );
+        values
     }
 }
 
@@ -160,4 +185,27 @@ mod tests {
         vol_targeting.update_position_multiplier();
         assert!(vol_targeting.get_position_multiplier() >= 0.5);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_reset_and_clone_replay_identically() {
+        let candles: Vec<Candle> = (0..60)
+            .map(|i| {
+                let close = 100.0 + (i as f64 * 0.7).sin() * 5.0 + i as f64 * 0.1;
+                Candle { timestamp: i, open: close, high: close + 1.0, low: close - 1.0, close, volume: 1.0, period: 60.0 }
+            })
+            .collect();
+        let run = |strategy: &mut dyn Strategy| -> Vec<String> {
+            candles.iter().map(|c| format!("{:?}", strategy.next(c))).collect()
+        };
+
+        let prototype = VolatilityTargeting::new(0.01, 20, Box::new(EmaCross::new(5, 20)), 0.1, 5.0);
+        let mut first: Box<dyn Strategy> = Box::new(prototype);
+        let mut copy = first.clone();
+
+        let expected = run(first.as_mut());
+        assert_eq!(run(copy.as_mut()), expected);
+
+        first.reset();
+        assert_eq!(run(first.as_mut()), expected);
+    }
+}
@@ -0,0 +1,65 @@
+use crate::types::{Candle, Signal};
+use indicator::Indicator;
+use super::{ATRBreakout, AtrSmoothing, Strategy, VolumeSpike};
+
+/// `ATRBreakout` whose upside breakouts must come with a volume spike.
+///
+/// Downside breakouts (`Sell`) are passed through unconfirmed so open
+/// positions are always closed.
+#[derive(Clone)]
+pub struct VolumeBreakout {
+    pub breakout: ATRBreakout,
+    pub volume_spike: VolumeSpike,
+}
+
+impl VolumeBreakout {
+    pub fn new(
+        lookback: usize,
+        atr_period: usize,
+        atr_multiplier: f64,
+        volume_period: usize,
+        volume_multiplier: f64,
+    ) -> Self {
+        Self {
+            breakout: ATRBreakout::new(lookback, atr_period, atr_multiplier),
+            volume_spike: VolumeSpike::new(volume_period, volume_multiplier),
+        }
+    }
+
+    pub fn with_atr_smoothing(mut self, smoothing: AtrSmoothing) -> Self {
+        self.breakout = self.breakout.with_atr_smoothing(smoothing);
+        self
+    }
+}
+
+impl Strategy for VolumeBreakout {
+    fn next(&mut self, candle: &Candle) -> Signal {
+        let spike = self.volume_spike.update(candle).unwrap_or(false);
+        match self.breakout.next(candle) {
+            Signal::Buy if spike => Signal::Buy,
+            Signal::Sell => Signal::Sell,
+            _ => Signal::Hold,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.breakout.reset();
+        self.volume_spike.reset();
+    }
+
+    fn box_clone(&self) -> Box<dyn Strategy> {
+        Box::new(self.clone())
+    }
+
+    fn warm_up(&self) -> usize {
+        self.breakout.warm_up().max(self.volume_spike.warm_up())
+    }
+
+    fn diagnostics(&self) -> Vec<(String, f64)> {
+        let mut values = self.breakout.diagnostics();
+        if let Some(ratio) = self.volume_spike.ratio() {
+            values.push(("volume_ratio".to_string(), ratio));
+        }
+        values
+    }
+}
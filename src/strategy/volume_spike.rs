@@ -0,0 +1,83 @@
+use moving_avg::Sma;
+use indicator::Indicator;
+
+use crate::types::Candle;
+
+/// Flags candles whose volume is at least `multiplier` times the average
+/// volume of the previous `period` candles.
+#[derive(Debug, Clone)]
+pub struct VolumeSpike {
+    average: Sma,
+    multiplier: f64,
+    ratio: Option<f64>,
+}
+
+impl VolumeSpike {
+    pub fn new(period: usize, multiplier: f64) -> Self {
+        Self {
+            average: Sma::new(period.max(1)),
+            multiplier,
+            ratio: None,
+        }
+    }
+
+    /// Returns whether this candle is a spike, once `period` earlier candles are known.
+    pub fn update(&mut self, candle: &Candle) -> Option<bool> {
+        // Compare against the average *before* including this candle
+        self.ratio = self.average.get().map(|avg| {
+            if avg > 0.0 { candle.volume / avg } else { 0.0 }
+        });
+        self.average.next(candle.volume);
+        self.ratio.map(|ratio| ratio >= self.multiplier)
+    }
+
+    /// Latest volume divided by the trailing average volume.
+    pub fn ratio(&self) -> Option<f64> {
+        self.ratio
+    }
+
+    pub fn reset(&mut self) {
+        self.average.reset();
+        self.ratio = None;
+    }
+}
+
+impl Indicator for VolumeSpike {
+    type Input = Candle;
+    type Output = bool;
+
+    fn next(&mut self, input: Candle) -> Option<bool> {
+        self.update(&input)
+    }
+
+    fn value(&self) -> Option<bool> {
+        self.ratio.map(|ratio| ratio >= self.multiplier)
+    }
+
+    fn reset(&mut self) {
+        VolumeSpike::reset(self)
+    }
+
+    /// The candle being tested comes after `period` earlier ones.
+    fn warm_up(&self) -> usize {
+        self.average.window + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_volume_spike() {
+        let mut spike = VolumeSpike::new(3, 2.0);
+        let flags: Vec<Option<bool>> = [10.0, 10.0, 10.0, 15.0, 40.0]
+            .iter()
+            .map(|&volume| {
+                spike.update(&Candle { timestamp: 0, open: 1.0, high: 1.0, low: 1.0, close: 1.0, volume, period: 1.0 })
+            })
+            .collect();
+        assert_eq!(flags, vec![None, None, None, Some(false), Some(true)]);
+        assert!((spike.ratio().unwrap() - 40.0 / (35.0 / 3.0)).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::VecDeque;
+
+use indicator::Indicator;
+
+use crate::types::Candle;
+
+/// What period the VWAP is accumulated over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VwapAnchor {
+    /// Restart at every session boundary, e.g. `86_400` seconds for UTC days.
+    Session { length_secs: i64 },
+    /// Over the last `window` candles.
+    Rolling { window: usize },
+}
+
+/// Volume Weighted Average Price of the typical price `(high + low + close) / 3`.
+#[derive(Debug, Clone)]
+pub struct Vwap {
+    anchor: VwapAnchor,
+    session: Option<i64>,
+    flows: VecDeque<(f64, f64)>,
+    pv_sum: f64,
+    volume_sum: f64,
+    value: Option<f64>,
+}
+
+impl Vwap {
+    pub fn new(anchor: VwapAnchor) -> Self {
+        Self {
+            anchor,
+            session: None,
+            flows: VecDeque::new(),
+            pv_sum: 0.0,
+            volume_sum: 0.0,
+            value: None,
+        }
+    }
+
+    /// VWAP restarting at every UTC midnight.
+    pub fn daily() -> Self {
+        Self::new(VwapAnchor::Session { length_secs: 86_400 })
+    }
+
+    pub fn rolling(window: usize) -> Self {
+        Self::new(VwapAnchor::Rolling { window: window.max(1) })
+    }
+
+    pub fn update(&mut self, candle: &Candle) -> Option<f64> {
+        let typical = (candle.high + candle.low + candle.close) / 3.0;
+        let pv = typical * candle.volume;
+
+        match self.anchor {
+            VwapAnchor::Session { length_secs } => {
+                let session = candle.timestamp.div_euclid(length_secs.max(1));
+                if self.session != Some(session) {
+                    self.session = Some(session);
+                    self.pv_sum = 0.0;
+                    self.volume_sum = 0.0;
+                }
+            }
+            VwapAnchor::Rolling { window } => {
+                self.flows.push_back((pv, candle.volume));
+                if self.flows.len() > window {
+                    if let Some((old_pv, old_volume)) = self.flows.pop_front() {
+                        self.pv_sum -= old_pv;
+                        self.volume_sum -= old_volume;
+                    }
+                }
+            }
+        }
+        self.pv_sum += pv;
+        self.volume_sum += candle.volume;
+
+        self.value = if self.volume_sum > 0.0 {
+            Some(self.pv_sum / self.volume_sum)
+        } else {
+            None
+        };
+        self.value
+    }
+
+    pub fn get(&self) -> Option<f64> {
+        self.value
+    }
+
+    pub fn reset(&mut self) {
+        self.session = None;
+        self.flows.clear();
+        self.pv_sum = 0.0;
+        self.volume_sum = 0.0;
+        self.value = None;
+    }
+}
+
+impl Indicator for Vwap {
+    type Input = Candle;
+    type Output = f64;
+
+    fn next(&mut self, input: Candle) -> Option<f64> {
+        self.update(&input)
+    }
+
+    fn value(&self) -> Option<f64> {
+        self.get()
+    }
+
+    fn reset(&mut self) {
+        Vwap::reset(self)
+    }
+
+    fn warm_up(&self) -> usize {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(timestamp: i64, price: f64, volume: f64) -> Candle {
+        Candle { timestamp, open: price, high: price, low: price, close: price, volume, period: 60.0 }
+    }
+
+    #[test]
+    fn test_session_vwap_restarts_each_day() {
+        let mut vwap = Vwap::daily();
+        assert_eq!(vwap.update(&candle(0, 10.0, 1.0)), Some(10.0));
+        assert_eq!(vwap.update(&candle(3_600, 20.0, 3.0)), Some(17.5));
+        assert_eq!(vwap.update(&candle(86_400, 30.0, 1.0)), Some(30.0));
+    }
+
+    #[test]
+    fn test_rolling_vwap() {
+        let mut vwap = Vwap::rolling(2);
+        vwap.update(&candle(0, 10.0, 1.0));
+        vwap.update(&candle(1, 20.0, 1.0));
+        assert_eq!(vwap.update(&candle(2, 40.0, 3.0)), Some(35.0));
+        assert_eq!(Vwap::rolling(2).update(&candle(0, 10.0, 0.0)), None);
+    }
+}
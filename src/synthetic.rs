@@ -0,0 +1,297 @@
+// src/synthetic.rs
+//
+// Synthetic OHLCV series for testing strategies without market data. Each
+// candle is built from a few intra-candle steps of the chosen price model, so
+// open/high/low/close are always consistent.
+
+use std::collections::HashMap;
+
+use crate::rng::Rng;
+use crate::types::Candle;
+
+/// Drift and volatility of one market regime, per candle (log returns).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Regime {
+    pub drift: f64,
+    pub volatility: f64,
+}
+
+/// Process driving the log price. All parameters are per candle.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PriceModel {
+    /// Geometric Brownian motion.
+    Gbm { drift: f64, volatility: f64 },
+    /// GARCH(1,1) volatility clustering: the candle variance is
+    /// `omega + alpha * last_shock^2 + beta * last_variance`.
+    Garch { drift: f64, omega: f64, alpha: f64, beta: f64 },
+    /// Markov regime switching: after every candle the process moves to
+    /// another regime (chosen uniformly) with `switch_probability`.
+    RegimeSwitching { regimes: Vec<Regime>, switch_probability: f64 },
+    /// GBM plus Poisson jumps with normally distributed log sizes (Merton).
+    Jump { drift: f64, volatility: f64, jump_intensity: f64, jump_mean: f64, jump_std: f64 },
+}
+
+impl PriceModel {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PriceModel::Gbm { .. } => "gbm",
+            PriceModel::Garch { .. } => "garch",
+            PriceModel::RegimeSwitching { .. } => "regime",
+            PriceModel::Jump { .. } => "jump",
+        }
+    }
+
+    /// Reasonable hourly-crypto-like defaults for every model.
+    pub fn presets() -> Vec<PriceModel> {
+        vec![
+            PriceModel::Gbm { drift: 0.0001, volatility: 0.01 },
+            PriceModel::Garch { drift: 0.0001, omega: 0.000002, alpha: 0.1, beta: 0.88 },
+            PriceModel::RegimeSwitching {
+                regimes: vec![
+                    Regime { drift: 0.002, volatility: 0.008 },
+                    Regime { drift: 0.0, volatility: 0.005 },
+                    Regime { drift: -0.003, volatility: 0.02 },
+                ],
+                switch_probability: 0.02,
+            },
+            PriceModel::Jump { drift: 0.0001, volatility: 0.008, jump_intensity: 0.02, jump_mean: -0.01, jump_std: 0.05 },
+        ]
+    }
+}
+
+/// Seeded candle generator; the same settings and seed give the same series.
+#[derive(Debug, Clone)]
+pub struct SyntheticMarket {
+    pub model: PriceModel,
+    pub start_price: f64,
+    pub start_timestamp: i64,
+    /// Candle length in minutes, as in the Kraken files.
+    pub period_minutes: u32,
+    /// Average volume per candle; larger moves trade more.
+    pub base_volume: f64,
+    /// Price path steps simulated inside each candle for the high and low.
+    pub steps_per_candle: usize,
+    rng: Rng,
+    price: f64,
+    variance: Option<f64>,
+    last_shock: f64,
+    regime: usize,
+    generated: i64,
+}
+
+impl SyntheticMarket {
+    pub fn new(model: PriceModel, seed: u64) -> Self {
+        Self {
+            model,
+            start_price: 100.0,
+            start_timestamp: 1_727_740_800, // 2024-10-01 00:00 UTC
+            period_minutes: 60,
+            base_volume: 1_000.0,
+            steps_per_candle: 8,
+            rng: Rng::new(seed),
+            price: 100.0,
+            variance: None,
+            last_shock: 0.0,
+            regime: 0,
+            generated: 0,
+        }
+    }
+
+    pub fn with_start_price(mut self, price: f64) -> Self {
+        self.start_price = price;
+        self.price = price;
+        self
+    }
+
+    pub fn with_start_timestamp(mut self, timestamp: i64) -> Self {
+        self.start_timestamp = timestamp;
+        self
+    }
+
+    pub fn with_period(mut self, minutes: u32) -> Self {
+        self.period_minutes = minutes.max(1);
+        self
+    }
+
+    pub fn with_base_volume(mut self, volume: f64) -> Self {
+        self.base_volume = volume;
+        self
+    }
+
+    /// Index of the regime the next candle is drawn from.
+    pub fn regime(&self) -> usize {
+        self.regime
+    }
+
+    /// Generate the next `count` candles, continuing from any earlier call.
+    pub fn generate(&mut self, count: usize) -> Vec<Candle> {
+        (0..count).map(|_| self.next_candle()).collect()
+    }
+
+    fn next_candle(&mut self) -> Candle {
+        let (drift, volatility) = self.candle_parameters();
+        let steps = self.steps_per_candle.max(1);
+        let dt = 1.0 / steps as f64;
+
+        let open = self.price;
+        let (mut high, mut low) = (open, open);
+        let mut log_return = 0.0;
+        for _ in 0..steps {
+            let mut step = (drift - 0.5 * volatility * volatility) * dt + volatility * dt.sqrt() * self.rng.normal();
+            if let PriceModel::Jump { jump_intensity, jump_mean, jump_std, .. } = self.model {
+                if self.rng.next_f64() < jump_intensity * dt {
+                    step += jump_mean + jump_std * self.rng.normal();
+                }
+            }
+            log_return += step;
+            self.price *= step.exp();
+            high = high.max(self.price);
+            low = low.min(self.price);
+        }
+        // Innovation around the expected log return (the steps' dt sum to one candle)
+        self.last_shock = log_return - (drift - 0.5 * volatility * volatility);
+        self.advance_regime();
+
+        // Volume rises with the size of the move relative to normal volatility
+        let surprise = if volatility > 0.0 { log_return.abs() / volatility } else { 0.0 };
+        let volume = self.base_volume * (1.0 + 0.5 * surprise) * (0.3 * self.rng.normal()).exp();
+
+        let timestamp = self.start_timestamp + self.generated * self.period_minutes as i64 * 60;
+        self.generated += 1;
+        Candle {
+            timestamp,
+            open,
+            high,
+            low,
+            close: self.price,
+            volume,
+            period: self.period_minutes as f64,
+        }
+    }
+
+    /// Drift and volatility for the candle about to be drawn.
+    fn candle_parameters(&mut self) -> (f64, f64) {
+        match &self.model {
+            PriceModel::Gbm { drift, volatility } | PriceModel::Jump { drift, volatility, .. } => (*drift, *volatility),
+            PriceModel::Garch { drift, omega, alpha, beta } => {
+                let variance = match self.variance {
+                    Some(prev) => omega + alpha * self.last_shock * self.last_shock + beta * prev,
+                    // Start at the long-run variance
+                    None => omega / (1.0 - alpha - beta).max(1e-6),
+                };
+                self.variance = Some(variance);
+                (*drift, variance.sqrt())
+            }
+            PriceModel::RegimeSwitching { regimes, .. } => {
+                let regime = regimes.get(self.regime).copied().unwrap_or(Regime { drift: 0.0, volatility: 0.0 });
+                (regime.drift, regime.volatility)
+            }
+        }
+    }
+
+    fn advance_regime(&mut self) {
+        if let PriceModel::RegimeSwitching { regimes, switch_probability } = &self.model {
+            if regimes.len() > 1 && self.rng.next_f64() < *switch_probability {
+                let other = self.rng.below(regimes.len() - 1);
+                self.regime = if other >= self.regime { other + 1 } else { other };
+            }
+        }
+    }
+}
+
+/// One series per preset model, keyed like data files (`synthetic_gbm`, ...),
+/// so the CLI can run the strategies on them instead of CSVs.
+pub fn preset_dataset(count: usize, seed: u64) -> HashMap<String, Vec<Candle>> {
+    PriceModel::presets()
+        .into_iter()
+        .enumerate()
+        .map(|(i, model)| {
+            let name = format!("synthetic_{}", model.name());
+            let candles = SyntheticMarket::new(model, seed.wrapping_add(i as u64)).generate(count);
+            (name, candles)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest::backtest;
+    use crate::strategy::{Params, Registry};
+    use crate::trade_model::TradeModel;
+
+    fn log_returns(candles: &[Candle]) -> Vec<f64> {
+        candles.windows(2).map(|pair| (pair[1].close / pair[0].close).ln()).collect()
+    }
+
+    #[test]
+    fn test_candles_are_consistent_and_reproducible() {
+        for model in PriceModel::presets() {
+            let candles = SyntheticMarket::new(model.clone(), 5).with_period(15).generate(500);
+            assert_eq!(candles.len(), 500);
+            for (i, c) in candles.iter().enumerate() {
+                assert!(c.low <= c.open.min(c.close) && c.high >= c.open.max(c.close), "{}", model.name());
+                assert!(c.low > 0.0 && c.volume > 0.0);
+                assert_eq!(c.timestamp, 1_727_740_800 + i as i64 * 900);
+                if i > 0 {
+                    assert_eq!(c.open, candles[i - 1].close);
+                }
+            }
+
+            let again = SyntheticMarket::new(model, 5).with_period(15).generate(500);
+            assert!(candles.iter().zip(&again).all(|(a, b)| a.close == b.close && a.volume == b.volume));
+        }
+    }
+
+    #[test]
+    fn test_gbm_volatility() {
+        let candles = SyntheticMarket::new(PriceModel::Gbm { drift: 0.0, volatility: 0.02 }, 1).generate(5_000);
+        let std = sharp_ratio::std_deviation(&log_returns(&candles));
+        assert!((std - 0.02).abs() < 0.001, "{std}");
+    }
+
+    #[test]
+    fn test_garch_clusters_and_jumps_fatten_tails() {
+        let garch = PriceModel::Garch { drift: 0.0, omega: 0.000002, alpha: 0.15, beta: 0.83 };
+        let returns = log_returns(&SyntheticMarket::new(garch, 2).generate(5_000));
+        // Squared returns are positively autocorrelated under volatility clustering
+        let squared: Vec<f64> = returns.iter().map(|r| r * r).collect();
+        let mean = sharp_ratio::average(&squared);
+        let cov: f64 = squared.windows(2).map(|w| (w[0] - mean) * (w[1] - mean)).sum();
+        let var: f64 = squared.iter().map(|s| (s - mean).powi(2)).sum();
+        assert!(cov / var > 0.05, "{}", cov / var);
+
+        let jump = PriceModel::Jump { drift: 0.0, volatility: 0.005, jump_intensity: 0.05, jump_mean: 0.0, jump_std: 0.05 };
+        let returns = log_returns(&SyntheticMarket::new(jump, 3).generate(5_000));
+        assert!(risk::excess_kurtosis(&returns) > 3.0);
+    }
+
+    #[test]
+    fn test_regime_switching_visits_all_regimes() {
+        let model = PriceModel::RegimeSwitching {
+            regimes: vec![Regime { drift: 0.01, volatility: 0.0 }, Regime { drift: -0.01, volatility: 0.0 }],
+            switch_probability: 0.1,
+        };
+        let mut market = SyntheticMarket::new(model, 4);
+        let mut seen = [false; 2];
+        for _ in 0..200 {
+            seen[market.regime()] = true;
+            market.generate(1);
+        }
+        assert_eq!(seen, [true, true]);
+    }
+
+    #[test]
+    fn test_builtin_strategies_run_on_every_model() {
+        let registry = Registry::builtin();
+        for (name, candles) in preset_dataset(400, 9) {
+            for spec in registry.specs() {
+                let mut strategy = registry.create(spec.name, &Params::new()).unwrap();
+                let mut trade_model = TradeModel::new(1000.0, 0.001, 0.001, 1.0);
+                let result = backtest(&candles, strategy.as_mut(), &mut trade_model);
+                let last = result.equity_curve.last().unwrap().equity;
+                assert!(last.is_finite() && last >= 0.0, "{} on {}", spec.name, name);
+            }
+        }
+    }
+}
@@ -0,0 +1,202 @@
+// src/terminal.rs
+
+use num_format::{Locale, ToFormattedString};
+
+const SPARK_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+#[derive(Clone, Copy)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+/// A plain-text table with aligned columns.
+pub struct Table {
+    headers: Vec<String>,
+    aligns: Vec<Align>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(columns: &[(&str, Align)]) -> Self {
+        Self {
+            headers: columns.iter().map(|(name, _)| name.to_string()).collect(),
+            aligns: columns.iter().map(|&(_, align)| align).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn add_row(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+
+    pub fn render(&self) -> String {
+        let mut widths: Vec<usize> = self.headers.iter().map(|h| h.chars().count()).collect();
+        for row in &self.rows {
+            for (i, cell) in row.iter().enumerate().take(widths.len()) {
+                widths[i] = widths[i].max(cell.chars().count());
+            }
+        }
+
+        let mut out = String::new();
+        out.push_str(&self.render_row(&self.headers, &widths));
+        let separator: Vec<String> = widths.iter().map(|&w| "-".repeat(w)).collect();
+        out.push_str(&separator.join("-+-"));
+        out.push('\n');
+        for row in &self.rows {
+            out.push_str(&self.render_row(row, &widths));
+        }
+        out
+    }
+
+    fn render_row(&self, row: &[String], widths: &[usize]) -> String {
+        let cells: Vec<String> = widths
+            .iter()
+            .enumerate()
+            .map(|(i, &width)| {
+                let cell = row.get(i).map(String::as_str).unwrap_or("");
+                let pad = " ".repeat(width.saturating_sub(cell.chars().count()));
+                match self.aligns[i] {
+                    Align::Left => format!("{}{}", cell, pad),
+                    Align::Right => format!("{}{}", pad, cell),
+                }
+            })
+            .collect();
+        format!("{}\n", cells.join(" | ").trim_end())
+    }
+}
+
+/// Format a number with thousands separators and a fixed number of decimals,
+/// e.g. `1234567.891` -> `1,234,567.89`.
+pub fn format_number(value: f64, decimals: usize) -> String {
+    if !value.is_finite() {
+        return value.to_string();
+    }
+    let rounded = format!("{:.*}", decimals, value.abs());
+    let (int_part, frac_part) = match rounded.split_once('.') {
+        Some((i, f)) => (i, Some(f)),
+        None => (rounded.as_str(), None),
+    };
+    let int_value: u64 = int_part.parse().unwrap_or(0);
+    let sign = if value < 0.0 && rounded.chars().any(|c| c.is_ascii_digit() && c != '0') { "-" } else { "" };
+    match frac_part {
+        Some(frac) => format!("{}{}.{}", sign, int_value.to_formatted_string(&Locale::en), frac),
+        None => format!("{}{}", sign, int_value.to_formatted_string(&Locale::en)),
+    }
+}
+
+/// Resample `values` into exactly `width` points by averaging buckets
+/// (or repeating points when there are fewer values than columns).
+fn resample(values: &[f64], width: usize) -> Vec<f64> {
+    if values.is_empty() || width == 0 {
+        return Vec::new();
+    }
+    (0..width)
+        .map(|col| {
+            let start = col * values.len() / width;
+            let end = ((col + 1) * values.len() / width).max(start + 1);
+            let bucket = &values[start..end.min(values.len())];
+            bucket.iter().sum::<f64>() / bucket.len() as f64
+        })
+        .collect()
+}
+
+fn bounds(values: &[f64]) -> (f64, f64) {
+    values
+        .iter()
+        .fold((f64::MAX, f64::MIN), |(lo, hi), &v| (lo.min(v), hi.max(v)))
+}
+
+/// One-line sparkline of `values`, at most `width` characters wide.
+pub fn sparkline(values: &[f64], width: usize) -> String {
+    let points = resample(values, width.min(values.len()));
+    let (min, max) = bounds(&points);
+    let range = max - min;
+    points
+        .iter()
+        .map(|&v| {
+            let level = if range > 0.0 {
+                ((v - min) / range * (SPARK_CHARS.len() - 1) as f64).round() as usize
+            } else {
+                SPARK_CHARS.len() / 2
+            };
+            SPARK_CHARS[level.min(SPARK_CHARS.len() - 1)]
+        })
+        .collect()
+}
+
+/// Multi-line ASCII chart of `values` with the min/max labelled on the y axis.
+pub fn line_chart(values: &[f64], width: usize, height: usize) -> String {
+    if values.is_empty() || width == 0 || height < 2 {
+        return String::new();
+    }
+    let points = resample(values, width);
+    let (min, max) = bounds(&points);
+    let range = if max > min { max - min } else { 1.0 };
+    let row_of = |v: f64| ((max - v) / range * (height - 1) as f64).round() as usize;
+
+    let mut grid = vec![vec![' '; points.len()]; height];
+    let mut prev_row: Option<usize> = None;
+    for (col, &v) in points.iter().enumerate() {
+        let row = row_of(v).min(height - 1);
+        // Connect vertical jumps so the line stays continuous
+        if let Some(prev) = prev_row {
+            let (top, bottom) = if prev < row { (prev + 1, row) } else { (row + 1, prev) };
+            for line in grid.iter_mut().take(bottom).skip(top) {
+                line[col] = '|';
+            }
+        }
+        grid[row][col] = '*';
+        prev_row = Some(row);
+    }
+
+    let max_label = format_number(max, 2);
+    let min_label = format_number(min, 2);
+    let label_width = max_label.len().max(min_label.len());
+
+    let mut out = String::new();
+    for (i, line) in grid.iter().enumerate() {
+        let label = if i == 0 {
+            max_label.as_str()
+        } else if i == height - 1 {
+            min_label.as_str()
+        } else {
+            ""
+        };
+        let body: String = line.iter().collect();
+        out.push_str(&format!("{:>w$} ┤{}\n", label, body.trim_end(), w = label_width));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(format_number(1234567.891, 2), "1,234,567.89");
+        assert_eq!(format_number(-1000.0, 0), "-1,000");
+        assert_eq!(format_number(0.00042, 5), "0.00042");
+        assert_eq!(format_number(-0.001, 2), "0.00");
+    }
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(sparkline(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0], 8), "▁▂▃▄▅▆▇█");
+        assert_eq!(sparkline(&[1.0, 2.0, 3.0], 20).chars().count(), 3);
+        assert_eq!(sparkline(&[], 10), "");
+    }
+
+    #[test]
+    fn test_table_alignment() {
+        let mut table = Table::new(&[("Strategy", Align::Left), ("Trades", Align::Right)]);
+        table.add_row(vec!["EMA".to_string(), "7".to_string()]);
+        table.add_row(vec!["Momentum".to_string(), "120".to_string()]);
+        let rendered = table.render();
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines[0], "Strategy | Trades");
+        assert_eq!(lines[2], "EMA      |      7");
+        assert_eq!(lines[3], "Momentum |    120");
+    }
+}
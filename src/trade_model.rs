@@ -1,4 +1,22 @@
-use crate::types::{Candle, Signal, Trade, Position};
+use crate::instrument::{self, Instrument};
+use crate::sizing::PositionSizer;
+use crate::types::{Candle, Lot, MarginCall, Signal, Trade, Position};
+
+/// Part of an order still to be filled, in units of the asset.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PendingOrder {
+    pub action: Signal,
+    pub size: f64,
+}
+
+/// Which lots a partial exit closes, and so the realized P&L it books.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LotMethod {
+    /// Oldest lots first.
+    Fifo,
+    /// Every lot in proportion, at the average entry price.
+    AverageCost,
+}
 
 pub struct TradeModel {
     pub cash: f64,
@@ -6,6 +24,37 @@ pub struct TradeModel {
     pub slippage: f64,
     pub fee: f64,
     pub min_trade_size: f64,
+    pub instrument: Instrument,
+    /// Net funding paid so far (negative when funding was received).
+    pub funding_paid: f64,
+    /// Spot margin: positions are `cash * leverage` and the borrowed part is
+    /// carried as negative cash. Perpetuals use their own settings instead.
+    pub leverage: f64,
+    /// Spot margin: equity share of the notional below which the position is liquidated.
+    pub maintenance_margin: f64,
+    /// Equity share of the notional below which a margin call is recorded.
+    pub margin_call: f64,
+    /// Largest share of a candle's volume one fill may take; `None` fills
+    /// any size at once.
+    pub max_volume_share: Option<f64>,
+    /// Unfilled part of the last order, continued on the next candles.
+    pub pending: Option<PendingOrder>,
+    /// Entry signals allowed per position; each entry uses `1 / max_entries`
+    /// of the buying power.
+    pub max_entries: usize,
+    /// Share of the position closed by each exit signal.
+    pub exit_fraction: f64,
+    pub lot_method: LotMethod,
+    /// Price P&L of all closed size so far, before fees and funding.
+    pub realized_pnl: f64,
+    pub fees_paid: f64,
+    /// Total cost of slippage versus the candle closes.
+    pub slippage_cost: f64,
+    /// Entry size policy; `None` puts `equity * leverage / max_entries` into
+    /// every entry.
+    pub sizer: Option<Box<dyn PositionSizer>>,
+    last_timestamp: Option<i64>,
+    in_margin_call: bool,
 }
 
 impl TradeModel {
@@ -16,66 +65,539 @@ impl TradeModel {
             slippage,
             fee,
             min_trade_size,
+            instrument: Instrument::Spot,
+            funding_paid: 0.0,
+            leverage: 1.0,
+            maintenance_margin: 0.0,
+            margin_call: 0.0,
+            max_volume_share: None,
+            pending: None,
+            max_entries: 1,
+            exit_fraction: 1.0,
+            lot_method: LotMethod::AverageCost,
+            realized_pnl: 0.0,
+            fees_paid: 0.0,
+            slippage_cost: 0.0,
+            sizer: None,
+            last_timestamp: None,
+            in_margin_call: false,
         }
     }
 
+    pub fn with_leverage(mut self, leverage: f64, maintenance_margin: f64) -> Self {
+        self.leverage = leverage.max(1.0);
+        self.maintenance_margin = maintenance_margin;
+        self
+    }
+
+    pub fn with_margin_call(mut self, margin_call: f64) -> Self {
+        self.margin_call = margin_call;
+        self
+    }
+
+    /// Cap every fill at `max_volume_share` of the candle volume, e.g. `0.1`.
+    pub fn with_liquidity(mut self, max_volume_share: f64) -> Self {
+        self.max_volume_share = Some(max_volume_share);
+        self
+    }
+
+    /// Allow adding to an open position on up to `max_entries` entry signals.
+    pub fn with_pyramiding(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries.max(1);
+        self
+    }
+
+    /// Close `exit_fraction` of the position per exit signal, e.g. `0.5`.
+    /// What is left is closed in full once it falls under `min_trade_size`.
+    pub fn with_partial_exits(mut self, exit_fraction: f64) -> Self {
+        self.exit_fraction = exit_fraction.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_lot_method(mut self, lot_method: LotMethod) -> Self {
+        self.lot_method = lot_method;
+        self
+    }
+
+    pub fn with_sizer(mut self, sizer: Box<dyn PositionSizer>) -> Self {
+        self.sizer = Some(sizer);
+        self
+    }
+
+    pub fn with_instrument(mut self, instrument: Instrument) -> Self {
+        self.instrument = instrument;
+        self
+    }
+
+    /// Per-candle bookkeeping before the strategy's signal is applied: pays
+    /// perpetual funding due since the previous candle and liquidates the
+    /// position if the candle's low (long) or high (short) reached the
+    /// liquidation price. Does nothing for unleveraged spot.
+    pub fn settle(&mut self, candle: &Candle) -> Option<Trade> {
+        let previous = self.last_timestamp.replace(candle.timestamp);
+        if let Some(sizer) = &mut self.sizer {
+            sizer.update(candle);
+        }
+        let pos = self.position.as_ref()?;
+        let is_long = pos.is_long;
+
+        if let (Instrument::Perpetual(perp), Some(previous)) = (&self.instrument, previous) {
+            let rate = perp.funding_between(previous, candle.timestamp);
+            let direction = if pos.is_long { 1.0 } else { -1.0 };
+            let payment = pos.size * candle.open * rate * direction;
+            self.cash -= payment;
+            self.funding_paid += payment;
+        }
+
+        let liquidation_price = self.liquidation_price()?;
+        let (hit, price) = if is_long {
+            // A gap through the liquidation price fills at the open
+            (candle.low <= liquidation_price, liquidation_price.min(candle.open))
+        } else {
+            (candle.high >= liquidation_price, liquidation_price.max(candle.open))
+        };
+        if !hit {
+            return None;
+        }
+
+        let size = self.position.as_ref().map_or(0.0, |pos| pos.size);
+        let trade = self.reduce(candle.timestamp, size, price, true);
+        self.record(&trade);
+        self.pending = None;
+        // Losses beyond the collateral are not carried
+        self.cash = self.cash.max(0.0);
+        Some(trade)
+    }
+
+    /// Price at which the open position is force-closed, if it can be.
+    pub fn liquidation_price(&self) -> Option<f64> {
+        let pos = self.position.as_ref()?;
+        match &self.instrument {
+            Instrument::Perpetual(perp) => Some(perp.liquidation_price(self.cash, pos.entry_price, pos.size, pos.is_long)),
+            // Only borrowed cash can be wiped out
+            Instrument::Spot if self.cash < 0.0 => {
+                let collateral = self.cash + pos.size * pos.entry_price;
+                Some(instrument::liquidation_price(collateral, pos.entry_price, pos.size, true, self.maintenance_margin))
+            }
+            Instrument::Spot => None,
+        }
+    }
+
+    /// Equity divided by the notional of the open position at `price`.
+    pub fn margin_ratio(&self, price: f64) -> Option<f64> {
+        let pos = self.position.as_ref()?;
+        let notional = pos.size * price;
+        (notional > 0.0).then(|| self.equity(price) / notional)
+    }
+
+    /// Margin call at the candle close, reported once each time the margin
+    /// ratio drops below `margin_call`.
+    pub fn check_margin(&mut self, candle: &Candle) -> Option<MarginCall> {
+        let margin_ratio = match self.margin_ratio(candle.close) {
+            Some(ratio) if ratio < self.margin_call => ratio,
+            _ => {
+                self.in_margin_call = false;
+                return None;
+            }
+        };
+        if self.in_margin_call {
+            return None;
+        }
+        self.in_margin_call = true;
+        Some(MarginCall {
+            timestamp: candle.timestamp,
+            margin_ratio,
+            equity: self.equity(candle.close),
+        })
+    }
+
+    /// Flat with too little cash left to open another position.
+    pub fn is_wiped_out(&self) -> bool {
+        self.position.is_none() && self.cash <= self.min_trade_size
+    }
+
     pub fn apply(&mut self, signal: Signal, candle: &Candle) -> Option<Trade> {
-        match signal {
-            Signal::Buy => {
-                if self.position.is_none() && self.cash > self.min_trade_size {
-                    // Open long position
-                    let price = candle.close * (1.0 + self.slippage);
-                    let size = self.cash / price;
-                    let fee = price * size * self.fee;
-                    self.cash = 0.0;
-                    self.position = Some(Position {
-                        entry_price: price,
-                        size,
-                        is_long: true,
-                    });
-                    Some(Trade {
-                        timestamp: candle.timestamp,
-                        action: Signal::Buy,
-                        price,
-                        size,
-                        fee,
-                    })
-                } else {
-                    None
-                }
+        let is_buy = match signal {
+            Signal::Buy => true,
+            Signal::Sell => false,
+            Signal::Hold => return self.fill_pending(candle),
+        };
+        match self.pending {
+            Some(order) if order.action == signal => return self.fill_pending(candle),
+            // A new signal the other way cancels what is left of the order
+            Some(_) => self.pending = None,
+            None => {}
+        }
+
+        let price = self.fill_price(candle, is_buy);
+        let equity = self.equity(price);
+        let entry_notional = match &self.sizer {
+            Some(sizer) => sizer.entry_notional(equity, price),
+            None => equity * self.effective_leverage() / self.max_entries as f64,
+        };
+        let entry_size = entry_notional / self.cost_per_unit(price);
+        let size = match &self.position {
+            // Spot `Sell` closes a long; perpetuals also close shorts with `Buy`
+            Some(pos) if pos.is_long != is_buy => {
+                let size = pos.size * self.exit_fraction;
+                if (pos.size - size) * price < self.min_trade_size { pos.size } else { size }
             }
-            Signal::Sell => {
-                if let Some(pos) = &self.position {
-                    if pos.is_long {
-                        // Close long position
-                        let price = candle.close * (1.0 - self.slippage);
-                        let size = pos.size;
-                        let fee = price * size * self.fee;
-                        let proceeds = price * size - fee;
-                        self.cash = proceeds;
-                        self.position = None;
-                        Some(Trade {
-                            timestamp: candle.timestamp,
-                            action: Signal::Sell,
-                            price,
-                            size,
-                            fee,
-                        })
-                    } else {
-                        None
-                    }
-                } else {
-                    None
+            Some(pos) if pos.entries < self.max_entries && entry_size * price > self.min_trade_size => entry_size,
+            Some(_) => return None,
+            None if self.can_open(is_buy) && self.cash > self.min_trade_size => entry_size,
+            None => return None,
+        };
+        self.fill(signal, size, candle, true)
+    }
+
+    /// Continue the unfilled part of an earlier order, if any.
+    fn fill_pending(&mut self, candle: &Candle) -> Option<Trade> {
+        let order = self.pending.take()?;
+        self.fill(order.action, order.size, candle, false)
+    }
+
+    /// Fill up to `size` at the candle close: closes (part of) a position in
+    /// the other direction or opens/adds to one in the same direction, within
+    /// the buying power and the liquidity cap. The rest is left pending.
+    fn fill(&mut self, action: Signal, size: f64, candle: &Candle, new_order: bool) -> Option<Trade> {
+        let is_buy = action == Signal::Buy;
+        let price = self.fill_price(candle, is_buy);
+        let closing = matches!(&self.position, Some(pos) if pos.is_long != is_buy);
+        let available = match &self.position {
+            Some(pos) if closing => pos.size,
+            _ if self.can_open(is_buy) => self.buying_power(price) / self.cost_per_unit(price),
+            _ => 0.0,
+        };
+        let size = size.min(available);
+        let filled = match self.max_volume_share {
+            Some(share) => size.min(share * candle.volume),
+            None => size,
+        };
+        let remaining = size - filled;
+        self.pending = (remaining > 0.0).then_some(PendingOrder { action, size: remaining });
+        if filled <= 0.0 {
+            return None;
+        }
+
+        let mut trade = if closing {
+            self.reduce(candle.timestamp, filled, price, false)
+        } else {
+            self.increase(candle.timestamp, is_buy, filled, price, new_order)
+        };
+        trade.remaining = remaining;
+        trade.slippage = (price - candle.close).abs() * filled;
+        self.slippage_cost += trade.slippage;
+        self.record(&trade);
+        Some(trade)
+    }
+
+    fn increase(&mut self, timestamp: i64, is_long: bool, size: f64, price: f64, new_entry: bool) -> Trade {
+        let fee = price * size * self.fee;
+        match self.instrument {
+            // Spot pays the notional, borrowing whatever the cash does not cover
+            Instrument::Spot => self.cash -= price * size + fee,
+            Instrument::Perpetual(_) => self.cash -= fee,
+        }
+        self.fees_paid += fee;
+        match &mut self.position {
+            Some(pos) => {
+                pos.entry_price = (pos.entry_price * pos.size + price * size) / (pos.size + size);
+                pos.size += size;
+                pos.lots.push(Lot { price, size });
+                if new_entry {
+                    pos.entries += 1;
                 }
             }
-            Signal::Hold => None,
+            None => {
+                self.position = Some(Position {
+                    entry_price: price,
+                    size,
+                    is_long,
+                    lots: vec![Lot { price, size }],
+                    entries: 1,
+                })
+            }
+        }
+        Trade {
+            timestamp,
+            action: if is_long { Signal::Buy } else { Signal::Sell },
+            price,
+            size,
+            fee,
+            liquidation: false,
+            closing: false,
+            remaining: 0.0,
+            realized_pnl: 0.0,
+            slippage: 0.0,
+        }
+    }
+
+    fn reduce(&mut self, timestamp: i64, size: f64, price: f64, liquidation: bool) -> Trade {
+        let pos = self.position.as_mut().expect("reduce needs an open position");
+        let size = size.min(pos.size);
+        let fee = price * size * self.fee;
+        let is_long = pos.is_long;
+        let cost = take_lots(pos, size, self.lot_method);
+        let pnl = if is_long { price * size - cost } else { cost - price * size };
+        self.realized_pnl += pnl;
+        self.fees_paid += fee;
+        match self.instrument {
+            // Proceeds repay any borrowed cash first
+            Instrument::Spot => self.cash += price * size - fee,
+            Instrument::Perpetual(_) => self.cash += pnl - fee,
+        }
+        if pos.size <= 0.0 {
+            self.position = None;
+        }
+        Trade {
+            timestamp,
+            action: if is_long { Signal::Sell } else { Signal::Buy },
+            price,
+            size,
+            fee,
+            liquidation,
+            closing: true,
+            remaining: 0.0,
+            realized_pnl: pnl,
+            slippage: 0.0,
         }
     }
 
+    fn fill_price(&self, candle: &Candle, is_buy: bool) -> f64 {
+        candle.close * if is_buy { 1.0 + self.slippage } else { 1.0 - self.slippage }
+    }
+
+    fn record(&mut self, trade: &Trade) {
+        if let Some(sizer) = &mut self.sizer {
+            sizer.record_trade(trade);
+        }
+    }
+
+    /// Buying power used per unit opened at `price`: spot also pays the fee
+    /// out of it, perpetuals only take the fee from the collateral.
+    fn cost_per_unit(&self, price: f64) -> f64 {
+        match self.instrument {
+            Instrument::Spot => price * (1.0 + self.fee),
+            Instrument::Perpetual(_) => price,
+        }
+    }
+
+    /// Spot is long only; perpetuals can also open shorts.
+    fn can_open(&self, is_buy: bool) -> bool {
+        is_buy || matches!(self.instrument, Instrument::Perpetual(_))
+    }
+
+    fn effective_leverage(&self) -> f64 {
+        match &self.instrument {
+            Instrument::Spot => self.leverage,
+            Instrument::Perpetual(perp) => perp.effective_leverage(),
+        }
+    }
+
+    /// Notional that can still be added to the position at `price`.
+    fn buying_power(&self, price: f64) -> f64 {
+        let notional = self.position.as_ref().map_or(0.0, |pos| pos.size * price);
+        (self.equity(price) * self.effective_leverage() - notional).max(0.0)
+    }
+
     pub fn equity(&self, price: f64) -> f64 {
-        match &self.position {
-            Some(pos) if pos.is_long => self.cash + pos.size * price,
+        match (&self.instrument, &self.position) {
+            (Instrument::Spot, Some(pos)) if pos.is_long => self.cash + pos.size * price,
+            (Instrument::Perpetual(_), Some(_)) => self.cash + self.unrealized_pnl(price),
             _ => self.cash,
         }
     }
-}
\ No newline at end of file
+
+    /// Price P&L of the open position at `price`, before exit fees.
+    pub fn unrealized_pnl(&self, price: f64) -> f64 {
+        match &self.position {
+            Some(pos) if pos.is_long => pos.size * (price - pos.entry_price),
+            Some(pos) => pos.size * (pos.entry_price - price),
+            None => 0.0,
+        }
+    }
+}
+
+/// Remove `size` from the position's lots and return the entry cost of the
+/// removed size; the average entry price is updated for what is left.
+fn take_lots(pos: &mut Position, size: f64, method: LotMethod) -> f64 {
+    let cost = match method {
+        LotMethod::AverageCost => {
+            let keep = if pos.size > 0.0 { 1.0 - size / pos.size } else { 0.0 };
+            for lot in &mut pos.lots {
+                lot.size *= keep;
+            }
+            pos.entry_price * size
+        }
+        LotMethod::Fifo => {
+            let mut left = size;
+            let mut cost = 0.0;
+            for lot in &mut pos.lots {
+                let taken = left.min(lot.size);
+                cost += taken * lot.price;
+                lot.size -= taken;
+                left -= taken;
+                if left <= 0.0 {
+                    break;
+                }
+            }
+            cost
+        }
+    };
+    pos.size -= size;
+    pos.lots.retain(|lot| lot.size > 0.0);
+    let held: f64 = pos.lots.iter().map(|lot| lot.size).sum();
+    if held > 0.0 {
+        pos.entry_price = pos.lots.iter().map(|lot| lot.price * lot.size).sum::<f64>() / held;
+    }
+    cost
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instrument::{FundingRate, Perpetual};
+
+    fn candle(timestamp: i64, low: f64, high: f64, close: f64) -> Candle {
+        Candle { timestamp, open: close, high, low, close, volume: 1.0, period: 60.0 }
+    }
+
+    #[test]
+    fn test_perpetual_short_with_leverage() {
+        let mut model = TradeModel::new(100.0, 0.0, 0.0, 1.0).with_instrument(Instrument::Perpetual(Perpetual::new(5.0, 0.1, 0.01)));
+        let open = model.apply(Signal::Sell, &candle(0, 99.0, 101.0, 100.0)).unwrap();
+        assert_eq!(open.action, Signal::Sell);
+        assert!((open.size - 5.0).abs() < 1e-12);
+        assert!((model.equity(90.0) - 150.0).abs() < 1e-9);
+
+        let close = model.apply(Signal::Buy, &candle(60, 89.0, 91.0, 90.0)).unwrap();
+        assert_eq!(close.action, Signal::Buy);
+        assert!(model.position.is_none());
+        assert!((model.cash - 150.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_perpetual_entry_not_sized_down_by_fee() {
+        // The fee comes out of the collateral, not the notional
+        let mut model = TradeModel::new(100.0, 0.0, 0.01, 1.0).with_instrument(Instrument::Perpetual(Perpetual::new(5.0, 0.1, 0.01)));
+        let open = model.apply(Signal::Buy, &candle(0, 99.0, 101.0, 100.0)).unwrap();
+        assert!((open.size - 5.0).abs() < 1e-12);
+        assert!((model.cash - 95.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_funding_and_liquidation() {
+        let perp = Perpetual::new(10.0, 0.1, 0.0).with_funding(vec![
+            FundingRate { timestamp: 30, rate: 0.001 },
+            FundingRate { timestamp: 90, rate: 0.001 },
+        ]);
+        let mut model = TradeModel::new(100.0, 0.0, 0.0, 1.0).with_instrument(Instrument::Perpetual(perp));
+
+        assert!(model.settle(&candle(0, 100.0, 100.0, 100.0)).is_none());
+        model.apply(Signal::Buy, &candle(0, 100.0, 100.0, 100.0));
+
+        // Long 10 units pays 0.1% of the 1000 notional
+        assert!(model.settle(&candle(60, 95.0, 101.0, 100.0)).is_none());
+        assert!((model.cash - 99.0).abs() < 1e-9);
+        assert!((model.funding_paid - 1.0).abs() < 1e-9);
+
+        // Collateral now ~98 after the second payment, so the long is wiped out just above 90
+        let liquidation = model.settle(&candle(120, 85.0, 100.0, 100.0)).unwrap();
+        assert!(liquidation.liquidation);
+        assert_eq!(liquidation.action, Signal::Sell);
+        assert!((liquidation.price - 90.2).abs() < 1e-9);
+        assert!(model.position.is_none());
+        assert!(model.cash.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_spot_margin_call_and_liquidation() {
+        let mut model = TradeModel::new(100.0, 0.0, 0.0, 1.0).with_leverage(4.0, 0.1).with_margin_call(0.2);
+        model.apply(Signal::Buy, &candle(0, 100.0, 100.0, 100.0));
+        assert!((model.cash + 300.0).abs() < 1e-9);
+        assert!((model.equity(100.0) - 100.0).abs() < 1e-9);
+
+        // 4 units at 90: equity 60 on a 360 notional
+        let call = model.check_margin(&candle(60, 90.0, 90.0, 90.0)).unwrap();
+        assert!((call.margin_ratio - 60.0 / 360.0).abs() < 1e-9);
+        assert!(model.check_margin(&candle(120, 90.0, 90.0, 90.0)).is_none());
+
+        // Equity 4p - 300 = 0.1 * 4p at p = 83.33
+        assert!(model.settle(&candle(180, 85.0, 91.0, 90.0)).is_none());
+        let liquidation = model.settle(&candle(240, 80.0, 90.0, 90.0)).unwrap();
+        assert!(liquidation.liquidation);
+        assert!((liquidation.price - 300.0 / 3.6).abs() < 1e-9);
+        assert!((model.cash - 100.0 / 3.0).abs() < 1e-9);
+        assert!(!model.is_wiped_out());
+    }
+
+    #[test]
+    fn test_fills_capped_by_volume() {
+        let mut model = TradeModel::new(1000.0, 0.0, 0.0, 1.0).with_liquidity(0.5);
+        let thin = |timestamp| Candle { volume: 4.0, ..candle(timestamp, 100.0, 100.0, 100.0) };
+
+        let first = model.apply(Signal::Buy, &thin(0)).unwrap();
+        assert_eq!((first.size, first.remaining), (2.0, 8.0));
+        let second = model.apply(Signal::Hold, &thin(60)).unwrap();
+        assert_eq!((second.size, second.remaining), (2.0, 6.0));
+        assert_eq!(model.position.as_ref().unwrap().size, 4.0);
+        assert!((model.equity(100.0) - 1000.0).abs() < 1e-9);
+
+        // Selling drops the rest of the buy and exits over two candles
+        let exit = model.apply(Signal::Sell, &thin(120)).unwrap();
+        assert_eq!((exit.action, exit.size, exit.remaining), (Signal::Sell, 2.0, 2.0));
+        // Buying again cancels the rest of the exit
+        let last = model.apply(Signal::Buy, &thin(180));
+        assert!(last.is_none() && model.pending.is_none());
+        assert_eq!(model.position.as_ref().unwrap().size, 2.0);
+    }
+
+    #[test]
+    fn test_pyramiding_and_partial_exits() {
+        // Realized and unrealized P&L after selling half of 5 @ 100 + 4 @ 125 at 150
+        for (method, realized, entry) in [(LotMethod::Fifo, 225.0, 550.0 / 4.5), (LotMethod::AverageCost, 175.0, 1000.0 / 9.0)] {
+            let mut model = TradeModel::new(1000.0, 0.0, 0.0, 1.0).with_pyramiding(2).with_partial_exits(0.5).with_lot_method(method);
+            assert_eq!(model.apply(Signal::Buy, &candle(0, 100.0, 100.0, 100.0)).unwrap().size, 5.0);
+            // Half the equity would be 4.5 units, but only 500 cash is left
+            assert_eq!(model.apply(Signal::Buy, &candle(60, 125.0, 125.0, 125.0)).unwrap().size, 4.0);
+            assert!(model.apply(Signal::Buy, &candle(120, 130.0, 130.0, 130.0)).is_none());
+            let pos = model.position.as_ref().unwrap();
+            assert_eq!((pos.entries, pos.lots.len()), (2, 2));
+            assert!((pos.entry_price - 1000.0 / 9.0).abs() < 1e-9);
+
+            let exit = model.apply(Signal::Sell, &candle(180, 150.0, 150.0, 150.0)).unwrap();
+            assert_eq!(exit.size, 4.5);
+            assert!((model.realized_pnl - realized).abs() < 1e-9, "{method:?}");
+            assert!((model.position.as_ref().unwrap().entry_price - entry).abs() < 1e-9, "{method:?}");
+            assert!((model.realized_pnl + model.unrealized_pnl(150.0) - 350.0).abs() < 1e-9);
+            assert!((model.equity(150.0) - 1350.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_pnl_fee_and_slippage_accounting() {
+        let mut model = TradeModel::new(1000.0, 0.01, 0.001, 1.0);
+        let entry = model.apply(Signal::Buy, &candle(0, 100.0, 100.0, 100.0)).unwrap();
+        // The fee is paid out of the cash, so the whole 1000 covers notional plus fee
+        assert!((entry.price * entry.size + entry.fee - 1000.0).abs() < 1e-9);
+        assert!(model.cash.abs() < 1e-9);
+        assert_eq!(entry.realized_pnl, 0.0);
+        assert!((model.unrealized_pnl(110.0) - entry.size * (110.0 - 101.0)).abs() < 1e-9);
+
+        let exit = model.apply(Signal::Sell, &candle(60, 110.0, 110.0, 110.0)).unwrap();
+        assert!((exit.realized_pnl - entry.size * (108.9 - 101.0)).abs() < 1e-9);
+        assert!((exit.slippage - entry.size * 1.1).abs() < 1e-9);
+        assert!((model.slippage_cost - entry.size * 2.1).abs() < 1e-9);
+        assert!((model.fees_paid - entry.fee - exit.fee).abs() < 1e-12);
+        assert!((model.cash - 1000.0 - (model.realized_pnl - model.fees_paid)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_spot_ignores_settle() {
+        let mut model = TradeModel::new(100.0, 0.0, 0.0, 1.0);
+        model.apply(Signal::Buy, &candle(0, 100.0, 100.0, 100.0));
+        assert!(model.settle(&candle(60, 1.0, 100.0, 2.0)).is_none());
+        assert!(model.apply(Signal::Sell, &candle(60, 1.0, 100.0, 2.0)).is_some());
+        assert!((model.cash - 2.0).abs() < 1e-9);
+    }
+}
@@ -1,6 +1,6 @@
 use serde::Deserialize;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Copy, Deserialize)]
 pub struct Candle {
     pub timestamp: i64,
     pub open: f64,
@@ -11,7 +11,7 @@ pub struct Candle {
     pub period: f64, // 1, 5, 15, 30, 60, 120, 240, 740, 1440
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Signal {
     Buy,
     Sell,
@@ -24,12 +24,27 @@ pub struct Trade {
     pub price: f64,
     pub size: f64,
     pub fee: f64,
+    /// Forced close by the trade model rather than the strategy's signal.
+    pub liquidation: bool,
+    /// Reduces or closes a position rather than opening or adding to one.
+    pub closing: bool,
+    /// Size of the order left unfilled after this fill; 0 once complete.
+    pub remaining: f64,
+    /// Price P&L of the size this fill closed, before fees; 0 for entries.
+    pub realized_pnl: f64,
+    /// Cost of the slippage on this fill versus the candle close.
+    pub slippage: f64,
 }
 
 pub struct Position {
+    /// Average entry price of the size still held.
     pub entry_price: f64,
     pub size: f64,
     pub is_long: bool,
+    /// Fills that built the position, oldest first, reduced as it is closed.
+    pub lots: Vec<Lot>,
+    /// Entry signals that added to the position (pyramiding).
+    pub entries: usize,
     /*
 | `is_long` | Meaning                                                    |
 | --------- | ---------------------------------------------------------- |
@@ -38,12 +53,43 @@ pub struct Position {
    */
 }
 
+/// One fill that opened or added to a position.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lot {
+    pub price: f64,
+    pub size: f64,
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct EquitySnapshot {
     pub timestamp: i64,
     pub cash: f64,
     pub equity: f64,
     pub position_value: f64,
     pub drawdown: f64,
+    /// Cumulative price P&L of closed size, before fees.
+    pub realized_pnl: f64,
+    /// Price P&L of the open position at the close.
+    pub unrealized_pnl: f64,
+    /// Cumulative fees paid.
+    pub fees: f64,
+    /// Cumulative slippage cost.
+    pub slippage: f64,
+}
+
+/// Equity fell below the margin call level of the open position.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MarginCall {
+    pub timestamp: i64,
+    /// Equity divided by the position notional at the candle close.
+    pub margin_ratio: f64,
+    pub equity: f64,
+}
+
+/// Strategy diagnostics (indicator values, internal state) recorded after a candle.
+pub struct DiagnosticsSnapshot {
+    pub timestamp: i64,
+    pub values: Vec<(String, f64)>,
 }
 
 /*